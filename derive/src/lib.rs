@@ -0,0 +1,81 @@
+//! Derive macros for [`light_bitmap`](https://docs.rs/light_bitmap).
+//!
+//! Not meant to be used directly, enable the `derive` feature of
+//! `light_bitmap` instead.
+
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, parse_macro_input};
+
+/// Derives `light_bitmap::BitIndexed` for a fieldless enum.
+///
+/// Variants are numbered in declaration order starting at 0, independent of
+/// any explicit discriminants.
+#[proc_macro_derive(BitIndexed)]
+pub fn derive_bit_indexed(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_bit_indexed(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_bit_indexed(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "BitIndexed can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "BitIndexed can't be derived for generic enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "BitIndexed requires at least one variant",
+        ));
+    }
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(Error::new_spanned(
+            variant,
+            "BitIndexed can only be derived for fieldless enums",
+        ));
+    }
+
+    let ident = &input.ident;
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+    let indices: Vec<_> = (0..variants.len()).collect();
+    let variant_count = variants.len();
+
+    Ok(quote! {
+        impl ::light_bitmap::BitIndexed for #ident {
+            const VARIANT_COUNT: usize = #variant_count;
+
+            #[inline]
+            fn to_index(self) -> usize {
+                match self {
+                    #(Self::#variants => #indices,)*
+                }
+            }
+
+            #[inline]
+            fn from_index(idx: usize) -> ::core::option::Option<Self> {
+                match idx {
+                    #(#indices => ::core::option::Option::Some(Self::#variants),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
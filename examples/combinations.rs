@@ -1,28 +1,24 @@
 use light_bitmap::{BitMap, bucket_count};
 
-fn combinations<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
-    idx: usize,
-    bitmap: &mut BitMap<BIT_COUNT, BUCKET_COUNT>,
-    data: &mut [bool],
-) {
-    if idx == data.len() {
-        for (idx, pick) in (0..data.len()).zip(bitmap.iter()) {
+fn main() {
+    const BIT_COUNT: usize = 9;
+    let all = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+
+    // every selection of the items
+    for selection in all.subsets() {
+        let mut data = [false; BIT_COUNT];
+        for (idx, pick) in selection.iter().enumerate() {
             data[idx] = pick;
         }
         println!("{data:?}");
-        return;
     }
-    if !bitmap.is_set(idx) {
-        bitmap.set(idx);
-        combinations(idx + 1, bitmap, data);
-        bitmap.unset(idx)
-    }
-    combinations(idx + 1, bitmap, data)
-}
 
-fn main() {
-    const BIT_COUNT: usize = 9;
-    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
-    let mut my_data = [false; BIT_COUNT];
-    combinations(0, &mut bitmap, &mut my_data);
+    // every selection of exactly three items
+    for selection in BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::combinations(3) {
+        let mut picks = [0; 3];
+        for (pick, idx) in picks.iter_mut().zip(selection.iter_ones()) {
+            *pick = idx;
+        }
+        println!("{picks:?}");
+    }
 }
@@ -0,0 +1,294 @@
+use crate::bitmap::BitMap;
+use core::fmt::{Display, Formatter};
+
+/// Order in which the bits of a value are laid out in the bitmap.
+///
+/// Either way a value occupies the bits from the cursor position upwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The least significant bit of a value goes to the lowest index.
+    Lsb0,
+    /// The most significant bit of a value goes to the lowest index.
+    Msb0,
+}
+
+/// Error returned by [`BitWriter`] and [`BitReader`] operations.
+///
+/// A failed operation leaves the bitmap and the cursor position unchanged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BitStreamError {
+    /// There are fewer bits left between the cursor and `BIT_COUNT` than the
+    /// operation needs.
+    OutOfSpace,
+    /// The value to write does not fit into the requested number of bits.
+    ValueOutOfRange,
+}
+
+impl Display for BitStreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            BitStreamError::OutOfSpace => "not enough bits left in the bitmap",
+            BitStreamError::ValueOutOfRange => "value does not fit into the bit width",
+        })
+    }
+}
+
+impl core::error::Error for BitStreamError {}
+
+/// Cursor that writes values into a bitmap at a moving position.
+///
+/// Each write overwrites the next bits (set and unset alike) and advances the
+/// position past them. Values are laid out according to the [`BitOrder`].
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, BitOrder, BitReader, BitWriter, bucket_count};
+///
+/// let mut frame = BitMap::<512, { bucket_count(512) }>::new();
+/// let mut writer = BitWriter::new(&mut frame, BitOrder::Lsb0);
+/// writer.write_bits(0b101, 3).unwrap();
+/// writer.write_bool(true).unwrap();
+/// writer.write_signed(-2, 12).unwrap();
+/// assert_eq!(writer.position(), 16);
+///
+/// let mut reader = BitReader::new(&frame, BitOrder::Lsb0);
+/// assert_eq!(reader.read_bits(3), Ok(0b101));
+/// assert_eq!(reader.read_bool(), Ok(true));
+/// assert_eq!(reader.read_signed(12), Ok(-2));
+/// ```
+pub struct BitWriter<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: &'bitmap mut BitMap<BIT_COUNT, BUCKET_COUNT>,
+    pos: usize,
+    order: BitOrder,
+}
+
+impl<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+    BitWriter<'bitmap, BIT_COUNT, BUCKET_COUNT>
+{
+    /// Creates a writer positioned at bit 0.
+    #[inline]
+    pub fn new(bitmap: &'bitmap mut BitMap<BIT_COUNT, BUCKET_COUNT>, order: BitOrder) -> Self {
+        Self {
+            bitmap,
+            pos: 0,
+            order,
+        }
+    }
+
+    /// Returns the index of the next bit to be written.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bits left until `BIT_COUNT`.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        BIT_COUNT - self.pos
+    }
+
+    /// Writes the low `bit_count` bits of `value`.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::ValueOutOfRange`] if `value` has bits set at
+    /// or above `bit_count` and [`BitStreamError::OutOfSpace`] if fewer than
+    /// `bit_count` bits are left.
+    ///
+    /// # Panics
+    /// Panics if `bit_count` is not in `1..=64`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, BitOrder, BitStreamError, BitWriter, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::new();
+    /// let mut writer = BitWriter::new(&mut bm, BitOrder::Msb0);
+    /// writer.write_bits(0b110, 3).unwrap();
+    /// assert_eq!(writer.write_bits(4, 2), Err(BitStreamError::ValueOutOfRange));
+    /// assert_eq!(writer.write_bits(0, 6), Err(BitStreamError::OutOfSpace));
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 1]));
+    /// ```
+    pub fn write_bits(&mut self, value: u64, bit_count: u32) -> Result<(), BitStreamError> {
+        assert_bit_count(bit_count);
+        if bit_count < 64 && value >> bit_count != 0 {
+            return Err(BitStreamError::ValueOutOfRange);
+        }
+        self.write_raw(value, bit_count)
+    }
+
+    /// Writes a single bit.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if no bits are left.
+    pub fn write_bool(&mut self, value: bool) -> Result<(), BitStreamError> {
+        self.write_raw(value as u64, 1)
+    }
+
+    /// Writes `value` as a two's complement number of `bit_count` bits.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::ValueOutOfRange`] if `value` is not
+    /// representable in `bit_count` bits and [`BitStreamError::OutOfSpace`]
+    /// if fewer than `bit_count` bits are left.
+    ///
+    /// # Panics
+    /// Panics if `bit_count` is not in `1..=64`.
+    pub fn write_signed(&mut self, value: i64, bit_count: u32) -> Result<(), BitStreamError> {
+        assert_bit_count(bit_count);
+        let unused = 64 - bit_count;
+        if value << unused >> unused != value {
+            return Err(BitStreamError::ValueOutOfRange);
+        }
+        self.write_raw(value as u64 & low_bits_mask(bit_count), bit_count)
+    }
+
+    /// Advances the position by `bit_count` bits, leaving them unchanged.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if fewer than `bit_count` bits
+    /// are left.
+    pub fn skip(&mut self, bit_count: usize) -> Result<(), BitStreamError> {
+        if bit_count > self.remaining() {
+            return Err(BitStreamError::OutOfSpace);
+        }
+        self.pos += bit_count;
+        Ok(())
+    }
+
+    fn write_raw(&mut self, value: u64, bit_count: u32) -> Result<(), BitStreamError> {
+        let len = bit_count as usize;
+        if len > self.remaining() {
+            return Err(BitStreamError::OutOfSpace);
+        }
+        let value = match self.order {
+            BitOrder::Lsb0 => value,
+            BitOrder::Msb0 => value.reverse_bits() >> (64 - bit_count),
+        };
+        self.bitmap.write_bits(self.pos, len, value);
+        self.pos += len;
+        Ok(())
+    }
+}
+
+/// Cursor that reads values from a bitmap at a moving position.
+///
+/// Each read consumes the next bits and advances the position past them.
+/// Values are expected in the layout of the given [`BitOrder`]. See
+/// [`BitWriter`] for an example.
+#[derive(Clone, Copy)]
+pub struct BitReader<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: &'bitmap BitMap<BIT_COUNT, BUCKET_COUNT>,
+    pos: usize,
+    order: BitOrder,
+}
+
+impl<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+    BitReader<'bitmap, BIT_COUNT, BUCKET_COUNT>
+{
+    /// Creates a reader positioned at bit 0.
+    #[inline]
+    pub fn new(bitmap: &'bitmap BitMap<BIT_COUNT, BUCKET_COUNT>, order: BitOrder) -> Self {
+        Self {
+            bitmap,
+            pos: 0,
+            order,
+        }
+    }
+
+    /// Returns the index of the next bit to be read.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bits left until `BIT_COUNT`.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        BIT_COUNT - self.pos
+    }
+
+    /// Reads `bit_count` bits as an unsigned value.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if fewer than `bit_count` bits
+    /// are left.
+    ///
+    /// # Panics
+    /// Panics if `bit_count` is not in `1..=64`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, BitOrder, BitReader, BitStreamError, bucket_count};
+    ///
+    /// let bm = BitMap::<8, { bucket_count(8) }>::from_ones_iter([0, 1]);
+    /// let mut reader = BitReader::new(&bm, BitOrder::Msb0);
+    /// assert_eq!(reader.read_bits(3), Ok(0b110));
+    /// assert_eq!(reader.read_bits(6), Err(BitStreamError::OutOfSpace));
+    /// assert_eq!(reader.read_bits(5), Ok(0));
+    /// ```
+    pub fn read_bits(&mut self, bit_count: u32) -> Result<u64, BitStreamError> {
+        assert_bit_count(bit_count);
+        self.read_raw(bit_count)
+    }
+
+    /// Reads a single bit.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if no bits are left.
+    pub fn read_bool(&mut self) -> Result<bool, BitStreamError> {
+        self.read_raw(1).map(|bit| bit != 0)
+    }
+
+    /// Reads `bit_count` bits as a two's complement number and sign-extends
+    /// it.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if fewer than `bit_count` bits
+    /// are left.
+    ///
+    /// # Panics
+    /// Panics if `bit_count` is not in `1..=64`.
+    pub fn read_signed(&mut self, bit_count: u32) -> Result<i64, BitStreamError> {
+        assert_bit_count(bit_count);
+        let unused = 64 - bit_count;
+        self.read_raw(bit_count)
+            .map(|raw| (raw << unused) as i64 >> unused)
+    }
+
+    /// Advances the position by `bit_count` bits without reading them.
+    ///
+    /// # Errors
+    /// Returns [`BitStreamError::OutOfSpace`] if fewer than `bit_count` bits
+    /// are left.
+    pub fn skip(&mut self, bit_count: usize) -> Result<(), BitStreamError> {
+        if bit_count > self.remaining() {
+            return Err(BitStreamError::OutOfSpace);
+        }
+        self.pos += bit_count;
+        Ok(())
+    }
+
+    fn read_raw(&mut self, bit_count: u32) -> Result<u64, BitStreamError> {
+        let len = bit_count as usize;
+        if len > self.remaining() {
+            return Err(BitStreamError::OutOfSpace);
+        }
+        let value = self.bitmap.read_bits(self.pos, len);
+        self.pos += len;
+        Ok(match self.order {
+            BitOrder::Lsb0 => value,
+            BitOrder::Msb0 => value.reverse_bits() >> (64 - bit_count),
+        })
+    }
+}
+
+fn assert_bit_count(bit_count: u32) {
+    assert!(
+        (1..=64).contains(&bit_count),
+        "Bit count {bit_count} must be between 1 and 64."
+    );
+}
+
+fn low_bits_mask(bit_count: u32) -> u64 {
+    u64::MAX >> (64 - bit_count)
+}
@@ -1,9 +1,11 @@
+use crate::bulk;
 use core::array::from_fn;
+use core::borrow::Borrow;
 use core::fmt::{Debug, Formatter};
 use core::iter::{FusedIterator, Iterator};
 use core::ops::{
     BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Range, Shl, ShlAssign,
-    Shr, ShrAssign,
+    Shr, ShrAssign, Sub, SubAssign,
 };
 
 /// Computes the number of buckets needed to store `bit_count` bits.
@@ -59,10 +61,23 @@ pub(crate) const fn ones_mask(start_bit: usize, width: usize) -> u8 {
 /// set via const expression with [`bucket_count`] to avoid unnecessary panics
 /// (see [`new`]).
 ///
-/// Internally stores bits in an array of `u8`.
+/// Internally stores bits in an array of `u8`. The bitmap is
+/// `#[repr(transparent)]` over that array, so it has the same layout as
+/// `[u8; BUCKET_COUNT]`.
 ///
 /// [`new`]: BitMap::new
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+#[repr(transparent)]
 pub struct BitMap<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(pub(crate) [u8; BUCKET_COUNT]);
 
 impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
@@ -388,6 +403,106 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
         bit
     }
 
+    /// Sets the bit at the given index.
+    ///
+    /// Returns the previous value of the bit (before setting it).
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::new();
+    /// assert_eq!(bm.test_and_set(4), false); // was free, now taken
+    /// assert_eq!(bm.test_and_set(4), true);  // was already taken
+    /// ```
+    #[inline]
+    pub fn test_and_set(&mut self, idx: usize) -> bool {
+        self.replace(idx, true)
+    }
+
+    /// Unsets the bit at the given index.
+    ///
+    /// Returns the previous value of the bit (before unsetting it).
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::with_all_set();
+    /// assert_eq!(bm.test_and_unset(4), true);
+    /// assert_eq!(bm.test_and_unset(4), false);
+    /// ```
+    #[inline]
+    pub fn test_and_unset(&mut self, idx: usize) -> bool {
+        self.replace(idx, false)
+    }
+
+    /// Sets the bit at the given index to `value`.
+    ///
+    /// Returns the previous value of the bit (before replacing it).
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::new();
+    /// assert_eq!(bm.replace(2, true), false);
+    /// assert_eq!(bm.replace(2, false), true);
+    /// assert!(!bm.is_set(2));
+    /// ```
+    #[inline]
+    pub fn replace(&mut self, idx: usize, value: bool) -> bool {
+        assert!(idx < BIT_COUNT, "Bit index {idx} out of bounds");
+        let (group_idx, item_idx) = Self::idxs(idx);
+        let bit = self.0[group_idx] & 1 << item_idx != 0;
+        self.0[group_idx] = self.0[group_idx] & !(1 << item_idx) | (value as u8) << item_idx;
+        bit
+    }
+
+    /// Swaps all bits with those of `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut a = BitMap::<8, { bucket_count(8) }>::from_ones_iter([1]);
+    /// let mut b = BitMap::<8, { bucket_count(8) }>::from_ones_iter([2, 3]);
+    /// a.swap_with(&mut b);
+    /// assert_eq!(a, BitMap::from_ones_iter([2, 3]));
+    /// assert_eq!(b, BitMap::from_ones_iter([1]));
+    /// ```
+    #[inline]
+    pub fn swap_with(&mut self, other: &mut Self) {
+        core::mem::swap(self, other);
+    }
+
+    /// Unsets all bits.
+    ///
+    /// Returns the previous bitmap (before clearing it).
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut pending = BitMap::<8, { bucket_count(8) }>::from_ones_iter([1, 5]);
+    /// let handled = pending.take();
+    /// assert_eq!(handled, BitMap::from_ones_iter([1, 5]));
+    /// assert_eq!(pending, BitMap::new());
+    /// ```
+    #[inline]
+    pub fn take(&mut self) -> Self {
+        core::mem::replace(self, Self([0u8; BUCKET_COUNT]))
+    }
+
     /// Returns `true` if the bit at the given index is set.
     ///
     /// # Panics
@@ -429,7 +544,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// assert_eq!(from_fn(|_| bm_iter.next().unwrap()), [true, false, true, false]);
     /// ```
     #[inline]
-    pub fn iter(&self) -> BitMapIter<BIT_COUNT, BUCKET_COUNT> {
+    pub fn iter(&self) -> BitMapIter<'_, BIT_COUNT, BUCKET_COUNT> {
         BitMapIter {
             bytes: &self.0,
             group_idx: 0,
@@ -455,7 +570,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// assert_eq!(ones, [0, 2, 4, 999, 999]);
     /// ```
     #[inline]
-    pub fn iter_ones(&self) -> IterOnes<BIT_COUNT, BUCKET_COUNT> {
+    pub fn iter_ones(&self) -> IterOnes<'_, BIT_COUNT, BUCKET_COUNT> {
         IterOnes {
             bytes: &self.0,
             byte_idx: 0,
@@ -482,7 +597,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// assert_eq!(zeros, [1, 3, 999, 999, 999]);
     /// ```
     #[inline]
-    pub fn iter_zeros(&self) -> IterZeros<BIT_COUNT, BUCKET_COUNT> {
+    pub fn iter_zeros(&self) -> IterZeros<'_, BIT_COUNT, BUCKET_COUNT> {
         IterZeros {
             bytes: &self.0,
             byte_idx: 0,
@@ -506,7 +621,9 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn bit_or(&self, other: &Self) -> Self {
-        Self(from_fn(|i| self.0[i] | other.0[i]))
+        let mut result = *self;
+        result.in_place_bit_or(other);
+        result
     }
 
     /// Performs an in-place bitwise OR with another bitmap.
@@ -524,9 +641,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn in_place_bit_or(&mut self, other: &Self) {
-        for (self_byte, other_byte) in self.0.iter_mut().zip(other.0.iter()) {
-            *self_byte |= other_byte
-        }
+        bulk::or(&mut self.0, &other.0);
     }
 
     /// Returns a new bitmap representing the bitwise AND of `self` and `other`.
@@ -544,7 +659,9 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn bit_and(&self, other: &Self) -> Self {
-        Self(from_fn(|i| self.0[i] & other.0[i]))
+        let mut result = *self;
+        result.in_place_bit_and(other);
+        result
     }
 
     /// Performs an in-place bitwise AND with another bitmap.
@@ -562,9 +679,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn in_place_bit_and(&mut self, other: &Self) {
-        for (self_byte, other_byte) in self.0.iter_mut().zip(other.0.iter()) {
-            *self_byte &= other_byte
-        }
+        bulk::and(&mut self.0, &other.0);
     }
 
     /// Returns a new bitmap representing the bitwise XOR of `self` and `other`.
@@ -582,7 +697,9 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn bit_xor(&self, other: &Self) -> Self {
-        Self(from_fn(|i| self.0[i] ^ other.0[i]))
+        let mut result = *self;
+        result.in_place_bit_xor(other);
+        result
     }
 
     /// Performs an in-place bitwise XOR with another bitmap.
@@ -600,9 +717,100 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn in_place_bit_xor(&mut self, other: &Self) {
-        for (self_byte, other_byte) in self.0.iter_mut().zip(other.0.iter()) {
-            *self_byte ^= other_byte
+        bulk::xor(&mut self.0, &other.0);
+    }
+
+    /// Returns a new bitmap with the bits of `self` that are not set in `other`
+    /// (set difference).
+    ///
+    /// Each bit in the result is set only if it is set in `self` and unset in
+    /// `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let a = BitMap::<4, { bucket_count(4) }>::from_slice(&[true, false, true, false]);
+    /// let b = BitMap::<4, { bucket_count(4) }>::from_slice(&[false, true, true, false]);
+    /// let c = a.bit_and_not(&b);
+    /// assert_eq!(c, BitMap::<4, { bucket_count(4) }>::from_slice(&[true, false, false, false]));
+    /// ```
+    #[inline]
+    pub fn bit_and_not(&self, other: &Self) -> Self {
+        let mut result = *self;
+        result.in_place_bit_and_not(other);
+        result
+    }
+
+    /// Unsets in-place all bits that are set in `other` (set difference).
+    ///
+    /// Each bit in `self` is updated to the result of `self & !other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut a = BitMap::<4, { bucket_count(4) }>::from_slice(&[true, false, true, false]);
+    /// let b = BitMap::<4, { bucket_count(4) }>::from_slice(&[false, true, true, false]);
+    /// a.in_place_bit_and_not(&b);
+    /// assert_eq!(a, BitMap::<4, { bucket_count(4) }>::from_slice(&[true, false, false, false]));
+    /// ```
+    #[inline]
+    pub fn in_place_bit_and_not(&mut self, other: &Self) {
+        bulk::and_not(&mut self.0, &other.0);
+    }
+
+    /// Returns the union (bitwise OR) of all bitmaps yielded by `bitmaps`.
+    ///
+    /// Accepts owned bitmaps as well as references and folds them in a single
+    /// pass. Returns an empty bitmap if `bitmaps` yields nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<10, { bucket_count(10) }>;
+    /// let parts = [Bm::from_ones_iter([0, 1]), Bm::from_ones_iter([1, 9])];
+    /// assert_eq!(Bm::union_all(parts.iter()), Bm::from_ones_iter([0, 1, 9]));
+    /// assert_eq!(Bm::union_all::<[Bm; 0], Bm>([]), Bm::new());
+    /// ```
+    pub fn union_all<I, B>(bitmaps: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut result = Self::new();
+        for bitmap in bitmaps {
+            result.in_place_bit_or(bitmap.borrow());
+        }
+        result
+    }
+
+    /// Returns the intersection (bitwise AND) of all bitmaps yielded by
+    /// `bitmaps`.
+    ///
+    /// Accepts owned bitmaps as well as references and folds them in a single
+    /// pass. Returns a bitmap with all bits set if `bitmaps` yields nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<10, { bucket_count(10) }>;
+    /// let parts = [Bm::from_ones_iter([0, 1, 9]), Bm::from_ones_iter([1, 9])];
+    /// assert_eq!(Bm::intersection_all(parts.iter()), Bm::from_ones_iter([1, 9]));
+    /// assert_eq!(Bm::intersection_all::<[Bm; 0], Bm>([]), Bm::with_all_set());
+    /// ```
+    pub fn intersection_all<I, B>(bitmaps: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut result = Self::with_all_set();
+        for bitmap in bitmaps {
+            result.in_place_bit_and(bitmap.borrow());
         }
+        result
     }
 
     /// Returns a new bitmap with each bit inverted (bitwise NOT).
@@ -656,7 +864,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// ```
     #[inline]
     pub fn popcount(&self) -> usize {
-        self.0.iter().map(|b| b.count_ones() as usize).sum()
+        bulk::popcount(&self.0)
     }
 
     /// Returns the index of the first set bit or `None` if all bits are unset.
@@ -677,17 +885,84 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     /// assert_eq!(bm.first_set_bit(), Some(2));
     /// ```
     pub fn first_set_bit(&self) -> Option<usize> {
-        for (i, byte) in self.0.iter().enumerate() {
-            if *byte != 0 {
-                let bit = byte.trailing_zeros() as usize;
-                return Some(i * 8 + bit);
-            }
+        bulk::first_non_zero(&self.0).map(|i| i * 8 + self.0[i].trailing_zeros() as usize)
+    }
+
+    /// Returns the index of the first set bit at or above `start`, skipping
+    /// whole unset buckets.
+    pub(crate) fn first_set_bit_from(&self, start: usize) -> Option<usize> {
+        self.first_bit_from(start, 0)
+    }
+
+    /// Returns the index of the first unset bit at or above `start`, skipping
+    /// whole set buckets.
+    pub(crate) fn first_unset_bit_from(&self, start: usize) -> Option<usize> {
+        self.first_bit_from(start, !0)
+    }
+
+    // `invert` is xor-ed onto every bucket, so searching for ones in the
+    // result finds zeros in `self` when `invert == !0`
+    fn first_bit_from(&self, start: usize, invert: u8) -> Option<usize> {
+        if start >= BIT_COUNT {
+            return None;
+        }
+        let (start_byte, start_bit) = Self::idxs(start);
+        let first = (self.0[start_byte] ^ invert) & !0u8 << start_bit;
+        let found = if first != 0 {
+            Some(start_byte * 8 + first.trailing_zeros() as usize)
+        } else {
+            self.0[start_byte + 1..]
+                .iter()
+                .position(|byte| byte ^ invert != 0)
+                .map(|offset| {
+                    let byte_idx = start_byte + 1 + offset;
+                    byte_idx * 8 + (self.0[byte_idx] ^ invert).trailing_zeros() as usize
+                })
+        };
+        found.filter(|&idx| idx < BIT_COUNT)
+    }
+
+    /// Returns the `len` bits starting at `start` as an integer with bit
+    /// `start` as its least significant bit, working on whole bucket chunks.
+    ///
+    /// The caller ensures `len <= 64` and `start + len <= BIT_COUNT`.
+    pub(crate) fn read_bits(&self, start: usize, len: usize) -> u64 {
+        let mut value = 0u64;
+        let mut done = 0;
+        while done < len {
+            let (byte_idx, bit_idx) = Self::idxs(start + done);
+            let chunk = (8 - bit_idx).min(len - done);
+            let bits = (self.0[byte_idx] & ones_mask(bit_idx, chunk)) >> bit_idx;
+            value |= (bits as u64) << done;
+            done += chunk;
+        }
+        value
+    }
+
+    /// Overwrites the `len` bits starting at `start` with the low `len` bits
+    /// of `value`, bit `start` receiving its least significant bit.
+    ///
+    /// The caller ensures `len <= 64` and `start + len <= BIT_COUNT`.
+    pub(crate) fn write_bits(&mut self, start: usize, len: usize, value: u64) {
+        let mut done = 0;
+        while done < len {
+            let (byte_idx, bit_idx) = Self::idxs(start + done);
+            let chunk = (8 - bit_idx).min(len - done);
+            let mask = ones_mask(bit_idx, chunk);
+            let bits = ((value >> done) as u8) << bit_idx;
+            self.0[byte_idx] = self.0[byte_idx] & !mask | bits & mask;
+            done += chunk;
         }
-        None
     }
 
     #[inline]
-    const fn clean_unused_bits(&mut self) {
+    pub(crate) fn has_clean_unused_bits(&self) -> bool {
+        let bits_in_last = BIT_COUNT % 8;
+        bits_in_last == 0 || self.0[BUCKET_COUNT - 1] >> bits_in_last == 0
+    }
+
+    #[inline]
+    pub(crate) const fn clean_unused_bits(&mut self) {
         let bits_in_last = BIT_COUNT % 8;
         if bits_in_last != 0 {
             let mask = (1 << bits_in_last) - 1;
@@ -764,7 +1039,7 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
             for i in 0..BUCKET_COUNT - byte_shift {
                 self.0[i] = self.0[i + byte_shift];
             }
-            for i in byte_shift..BUCKET_COUNT {
+            for i in BUCKET_COUNT - byte_shift..BUCKET_COUNT {
                 self.0[i] = 0;
             }
         }
@@ -795,6 +1070,8 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET
     pub fn rotate_left(&mut self, n: usize) {
         // avoid division by zero
         runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        // `is_multiple_of` needs Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
         if n % BIT_COUNT == 0 {
             return;
         }
@@ -905,6 +1182,109 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FromIterator<bool>
     }
 }
 
+/// Constructs a bitmap by setting the indices yielded by the iterator.
+///
+/// Equivalent to [`BitMap::from_ones_iter()`].
+///
+/// # Panics
+/// Panics if any index is out of bounds (i.e., `>= BIT_COUNT`), if
+/// `BIT_COUNT == 0` or if `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm: BitMap<10, { bucket_count(10) }> = (2..5).collect();
+/// assert_eq!(bm, BitMap::from_ones_iter([2, 3, 4]));
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FromIterator<usize>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        Self::from_ones_iter(iter)
+    }
+}
+
+/// Sets the bits at the indices yielded by the iterator.
+///
+/// Bits that are already set stay set.
+///
+/// # Panics
+/// Panics if any index is out of bounds (i.e., `>= BIT_COUNT`).
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0]);
+/// bm.extend([3, 9]);
+/// assert_eq!(bm, BitMap::from_ones_iter([0, 3, 9]));
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Extend<usize>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn extend<T: IntoIterator<Item = usize>>(&mut self, iter: T) {
+        for idx in iter {
+            self.set(idx);
+        }
+    }
+}
+
+/// Sets the bits at the indices yielded by the iterator.
+///
+/// See the [`Extend<usize>`] impl.
+impl<'idx, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Extend<&'idx usize>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn extend<T: IntoIterator<Item = &'idx usize>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Sets bit `i` if the `i`-th yielded element is `true`, starting at index 0.
+///
+/// Bits are only ever set, never unset: `false` leaves the bit as it is, so
+/// extending ORs the yielded pattern into the bitmap. The iterator may yield
+/// fewer than `BIT_COUNT` elements, in which case the remaining bits are left
+/// untouched.
+///
+/// # Panics
+/// Panics if the iterator yields more than `BIT_COUNT` elements.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 9]);
+/// bm.extend([false, true, true]);
+/// assert_eq!(bm, BitMap::from_ones_iter([0, 1, 2, 9]));
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Extend<bool>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn extend<T: IntoIterator<Item = bool>>(&mut self, iter: T) {
+        for (idx, bit) in iter.into_iter().enumerate() {
+            if idx >= BIT_COUNT {
+                panic!("Iterator yielded more than {BIT_COUNT} elements");
+            }
+            if bit {
+                self.set(idx);
+            }
+        }
+    }
+}
+
+/// Sets bit `i` if the `i`-th yielded element is `true`, starting at index 0.
+///
+/// See the [`Extend<bool>`] impl.
+impl<'bit, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Extend<&'bit bool>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn extend<T: IntoIterator<Item = &'bit bool>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
 impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitAnd for BitMap<BIT_COUNT, BUCKET_COUNT> {
     type Output = Self;
 
@@ -999,6 +1379,159 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> ShrAssign<usize>
     }
 }
 
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Sub for BitMap<BIT_COUNT, BUCKET_COUNT> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.bit_and_not(&rhs)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> SubAssign
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.in_place_bit_and_not(&rhs)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Not for &BitMap<BIT_COUNT, BUCKET_COUNT> {
+    type Output = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+    fn not(self) -> Self::Output {
+        self.bit_not()
+    }
+}
+
+/// Implements a binary operator for borrowed operands on either side and its
+/// assign operator for a borrowed right-hand side.
+macro_rules! impl_ref_binary_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $method:ident, $in_place:ident) => {
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+            $op<&BitMap<BIT_COUNT, BUCKET_COUNT>> for BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = Self;
+
+            fn $op_fn(self, rhs: &Self) -> Self::Output {
+                self.$method(rhs)
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> $op<BitMap<BIT_COUNT, BUCKET_COUNT>>
+            for &BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+            fn $op_fn(self, rhs: BitMap<BIT_COUNT, BUCKET_COUNT>) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+            $op<&BitMap<BIT_COUNT, BUCKET_COUNT>> for &BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+            fn $op_fn(self, rhs: &BitMap<BIT_COUNT, BUCKET_COUNT>) -> Self::Output {
+                self.$method(rhs)
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+            $assign<&BitMap<BIT_COUNT, BUCKET_COUNT>> for BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            fn $assign_fn(&mut self, rhs: &Self) {
+                self.$in_place(rhs)
+            }
+        }
+    };
+}
+
+impl_ref_binary_op!(
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    bit_and,
+    in_place_bit_and
+);
+impl_ref_binary_op!(
+    BitOr,
+    bitor,
+    BitOrAssign,
+    bitor_assign,
+    bit_or,
+    in_place_bit_or
+);
+impl_ref_binary_op!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    bit_xor,
+    in_place_bit_xor
+);
+impl_ref_binary_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    bit_and_not,
+    in_place_bit_and_not
+);
+
+/// Implements a shift operator for a borrowed bitmap and/or a borrowed shift
+/// amount and its assign operator for a borrowed shift amount.
+macro_rules! impl_ref_shift_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $method:ident) => {
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> $op<usize>
+            for &BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+            fn $op_fn(self, rhs: usize) -> Self::Output {
+                let mut result = *self;
+                result.$method(rhs);
+                result
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> $op<&usize>
+            for BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = Self;
+
+            fn $op_fn(mut self, rhs: &usize) -> Self::Output {
+                self.$method(*rhs);
+                self
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> $op<&usize>
+            for &BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            type Output = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+            fn $op_fn(self, rhs: &usize) -> Self::Output {
+                let mut result = *self;
+                result.$method(*rhs);
+                result
+            }
+        }
+
+        impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> $assign<&usize>
+            for BitMap<BIT_COUNT, BUCKET_COUNT>
+        {
+            fn $assign_fn(&mut self, rhs: &usize) {
+                self.$method(*rhs);
+            }
+        }
+    };
+}
+
+impl_ref_shift_op!(Shl, shl, ShlAssign, shl_assign, shift_left);
+impl_ref_shift_op!(Shr, shr, ShrAssign, shr_assign, shift_right);
+
 /// Iterator over all bits in the bitmap as `bool` values.
 ///
 /// Yields `true` for set bits and `false` for unset bits, starting from index 0.
@@ -1055,22 +1588,26 @@ impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.byte_idx < BUCKET_COUNT {
-            if self.current != 0 {
-                let tz = self.current.trailing_zeros() as usize;
-                let idx = self.base_bit_idx + tz;
-                if idx >= BIT_COUNT {
-                    return None;
-                }
-                self.current &= self.current - 1; // unset LSB
-                return Some(idx);
-            }
+        if self.current == 0 {
+            // jump straight to the next non-zero bucket
+            let next_byte = self.byte_idx + 1;
+            let rest = self.bytes.get(next_byte..).unwrap_or(&[]);
+            let Some(offset) = bulk::first_non_zero(rest) else {
+                self.byte_idx = BUCKET_COUNT;
+                return None;
+            };
+            self.byte_idx = next_byte + offset;
+            self.base_bit_idx = self.byte_idx * 8;
+            self.current = self.bytes[self.byte_idx];
+        }
 
-            self.byte_idx += 1;
-            self.base_bit_idx += 8;
-            self.current = *self.bytes.get(self.byte_idx).unwrap_or(&0);
+        let tz = self.current.trailing_zeros() as usize;
+        let idx = self.base_bit_idx + tz;
+        if idx >= BIT_COUNT {
+            return None;
         }
-        None
+        self.current &= self.current - 1; // unset LSB
+        Some(idx)
     }
 }
 
@@ -0,0 +1,82 @@
+//! Loops over whole bucket arrays.
+//!
+//! With the `simd` feature the buckets are processed in `core::simd` vectors
+//! of [`LANES`] bytes, with the scalar loop handling the remainder. Both paths
+//! produce identical results.
+
+#[cfg(feature = "simd")]
+use core::simd::prelude::*;
+
+#[cfg(feature = "simd")]
+const LANES: usize = 32;
+
+macro_rules! impl_in_place_op {
+    ($name:ident, |$a:ident, $b:ident| $op:expr) => {
+        #[inline]
+        pub(crate) fn $name(dst: &mut [u8], src: &[u8]) {
+            #[cfg(feature = "simd")]
+            let (dst, src) = {
+                let (dst_chunks, dst_rest) = dst.as_chunks_mut::<LANES>();
+                let (src_chunks, src_rest) = src.as_chunks::<LANES>();
+                for (dst_chunk, src_chunk) in dst_chunks.iter_mut().zip(src_chunks) {
+                    let $a = Simd::from_array(*dst_chunk);
+                    let $b = Simd::from_array(*src_chunk);
+                    *dst_chunk = ($op).to_array();
+                }
+                (dst_rest, src_rest)
+            };
+            for (dst_byte, &src_byte) in dst.iter_mut().zip(src) {
+                let ($a, $b) = (*dst_byte, src_byte);
+                *dst_byte = $op;
+            }
+        }
+    };
+}
+
+impl_in_place_op!(and, |a, b| a & b);
+impl_in_place_op!(or, |a, b| a | b);
+impl_in_place_op!(xor, |a, b| a ^ b);
+impl_in_place_op!(and_not, |a, b| a & !b);
+
+/// Returns the number of set bits in `bytes`.
+#[inline]
+pub(crate) fn popcount(bytes: &[u8]) -> usize {
+    #[cfg(feature = "simd")]
+    {
+        let (chunks, rest) = bytes.as_chunks::<LANES>();
+        let vector_count: usize = chunks
+            .iter()
+            .map(|chunk| {
+                let ones = Simd::from_array(*chunk).count_ones();
+                ones.cast::<u16>().reduce_sum() as usize
+            })
+            .sum();
+        vector_count + scalar_popcount(rest)
+    }
+    #[cfg(not(feature = "simd"))]
+    scalar_popcount(bytes)
+}
+
+fn scalar_popcount(bytes: &[u8]) -> usize {
+    bytes.iter().map(|b| b.count_ones() as usize).sum()
+}
+
+/// Returns the index of the first non-zero byte in `bytes`, skipping over
+/// zero buckets.
+#[inline]
+pub(crate) fn first_non_zero(bytes: &[u8]) -> Option<usize> {
+    #[cfg(feature = "simd")]
+    {
+        let (chunks, rest) = bytes.as_chunks::<LANES>();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let non_zero = Simd::from_array(*chunk).simd_ne(Simd::splat(0));
+            if let Some(lane) = non_zero.first_set() {
+                return Some(i * LANES + lane);
+            }
+        }
+        let offset = chunks.len() * LANES;
+        rest.iter().position(|&b| b != 0).map(|i| offset + i)
+    }
+    #[cfg(not(feature = "simd"))]
+    bytes.iter().position(|&b| b != 0)
+}
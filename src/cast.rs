@@ -0,0 +1,80 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Reinterprets `bytes` as a bitmap without copying.
+    ///
+    /// Returns `None` if `bytes` is not exactly `BUCKET_COUNT` bytes long or
+    /// if it sets any bit beyond `BIT_COUNT`. Prefer this over the unchecked
+    /// casts of `bytemuck` and `zerocopy` for untrusted input, as those accept
+    /// such bits and produce a bitmap that e.g. reports a wrong `popcount`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<12, { bucket_count(12) }>;
+    /// let bm = Bm::ref_from_bytes_checked(&[0b0000_0101, 0b0000_1000]).unwrap();
+    /// assert_eq!(*bm, Bm::from_ones_iter([0, 2, 11]));
+    /// assert!(Bm::ref_from_bytes_checked(&[0, 0b0001_0000]).is_none());
+    /// assert!(Bm::ref_from_bytes_checked(&[0]).is_none());
+    /// ```
+    pub fn ref_from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        let bitmap = cast_ref(bytes)?;
+        bitmap.has_clean_unused_bits().then_some(bitmap)
+    }
+
+    /// Reinterprets `bytes` as a mutable bitmap without copying.
+    ///
+    /// Returns `None` if `bytes` is not exactly `BUCKET_COUNT` bytes long or
+    /// if it sets any bit beyond `BIT_COUNT`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bytes = [0u8; 2];
+    /// let bm = BitMap::<12, { bucket_count(12) }>::mut_from_bytes_checked(&mut bytes).unwrap();
+    /// bm.set(11);
+    /// assert_eq!(bytes, [0, 0b0000_1000]);
+    /// ```
+    pub fn mut_from_bytes_checked(bytes: &mut [u8]) -> Option<&mut Self> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        let bitmap = cast_mut(bytes)?;
+        bitmap.has_clean_unused_bits().then_some(bitmap)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+fn cast_ref<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bytes: &[u8],
+) -> Option<&BitMap<BIT_COUNT, BUCKET_COUNT>> {
+    bytemuck::try_from_bytes(bytes).ok()
+}
+
+#[cfg(feature = "bytemuck")]
+fn cast_mut<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bytes: &mut [u8],
+) -> Option<&mut BitMap<BIT_COUNT, BUCKET_COUNT>> {
+    bytemuck::try_from_bytes_mut(bytes).ok()
+}
+
+#[cfg(not(feature = "bytemuck"))]
+fn cast_ref<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bytes: &[u8],
+) -> Option<&BitMap<BIT_COUNT, BUCKET_COUNT>> {
+    zerocopy::FromBytes::ref_from_bytes(bytes).ok()
+}
+
+#[cfg(not(feature = "bytemuck"))]
+fn cast_mut<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bytes: &mut [u8],
+) -> Option<&mut BitMap<BIT_COUNT, BUCKET_COUNT>> {
+    zerocopy::FromBytes::mut_from_bytes(bytes).ok()
+}
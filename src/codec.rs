@@ -0,0 +1,95 @@
+use core::fmt::{Display, Formatter};
+
+/// Error returned when encoding a bitmap into or decoding it from bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CodecError {
+    /// The output buffer is too small for the encoded bitmap.
+    BufferTooSmall,
+    /// The input ends before the bitmap is complete.
+    UnexpectedEnd,
+    /// A varint is longer than needed or does not fit into a `usize`.
+    InvalidVarint,
+    /// A run is empty or reaches beyond `BIT_COUNT`.
+    InvalidRun,
+    /// An index or a set bit lies beyond `BIT_COUNT`.
+    OutOfRange,
+    /// The leading tag byte names no known encoding.
+    InvalidTag,
+    /// The input continues after the bitmap is complete.
+    TrailingBytes,
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            CodecError::BufferTooSmall => "output buffer too small",
+            CodecError::UnexpectedEnd => "unexpected end of input",
+            CodecError::InvalidVarint => "invalid varint",
+            CodecError::InvalidRun => "run empty or out of bounds",
+            CodecError::OutOfRange => "bit index out of range",
+            CodecError::InvalidTag => "unknown encoding tag",
+            CodecError::TrailingBytes => "trailing bytes after the bitmap",
+        })
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+/// Writes `value` as an unsigned LEB128 varint at `out[*pos..]` and advances
+/// `pos` past it.
+pub(crate) fn write_varint(
+    out: &mut [u8],
+    pos: &mut usize,
+    mut value: usize,
+) -> Result<(), CodecError> {
+    loop {
+        let byte = out.get_mut(*pos).ok_or(CodecError::BufferTooSmall)?;
+        *pos += 1;
+        if value < 0x80 {
+            *byte = value as u8;
+            return Ok(());
+        }
+        *byte = value as u8 | 0x80;
+        value >>= 7;
+    }
+}
+
+/// Returns the number of bytes `value` takes as an unsigned LEB128 varint.
+pub(crate) fn varint_len(value: usize) -> usize {
+    (usize::BITS - value.leading_zeros()).div_ceil(7).max(1) as usize
+}
+
+/// Incrementally reads an unsigned LEB128 varint, one byte at a time.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct VarintReader {
+    value: usize,
+    shift: u32,
+}
+
+impl VarintReader {
+    /// Consumes the next byte and returns the value once it is complete.
+    pub(crate) fn push(&mut self, byte: u8) -> Result<Option<usize>, CodecError> {
+        let bits = (byte & 0x7f) as usize;
+        // rejects overflowing and overlong varints alike, so every value has
+        // exactly one encoding
+        if self.shift >= usize::BITS
+            || bits << self.shift >> self.shift != bits
+            || (self.shift > 0 && byte == 0)
+        {
+            return Err(CodecError::InvalidVarint);
+        }
+        self.value |= bits << self.shift;
+        if byte & 0x80 != 0 {
+            self.shift += 7;
+            return Ok(None);
+        }
+        let value = self.value;
+        *self = Self::default();
+        Ok(Some(value))
+    }
+
+    /// Returns `true` if a varint has been started but not completed.
+    pub(crate) fn is_partial(&self) -> bool {
+        self.shift > 0
+    }
+}
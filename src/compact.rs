@@ -0,0 +1,250 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+use crate::codec::{CodecError, VarintReader, varint_len, write_varint};
+
+const TAG_RAW: u8 = 0;
+const TAG_INDEX_LIST: u8 = 1;
+const TAG_INVERTED_INDEX_LIST: u8 = 2;
+const TAG_RLE: u8 = 3;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Encodes the indices of all set bits into `out` and returns the number
+    /// of bytes written.
+    ///
+    /// The encoding is a sequence of unsigned LEB128 varints, one per set bit
+    /// in ascending order, each giving the number of unset bits skipped since
+    /// the previous set bit (or since bit 0 for the first one). An empty
+    /// bitmap encodes as no bytes at all. `BIT_COUNT` and the number of set
+    /// bits are not stored, the input ends with the last varint.
+    ///
+    /// Takes one byte per set bit as long as the set bits are less than 128
+    /// bits apart, which makes it small for sparse bitmaps.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::BufferTooSmall`] if `out` is too small. A buffer
+    /// of `BIT_COUNT` bytes always suffices.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<1000, { bucket_count(1000) }>;
+    /// let bm = Bm::from_ones_iter([3, 4, 300]);
+    /// let mut buf = [0u8; 16];
+    /// let len = bm.encode_index_list(&mut buf).unwrap();
+    /// assert_eq!(&buf[..len], &[3, 0, 0xa7, 0x02]);
+    /// assert_eq!(Bm::decode_index_list(&buf[..len]), Ok(bm));
+    /// ```
+    pub fn encode_index_list(&self, out: &mut [u8]) -> Result<usize, CodecError> {
+        encode_indices(self.iter_ones(), out)
+    }
+
+    /// Decodes a bitmap from the index list written by
+    /// [`encode_index_list`].
+    ///
+    /// # Errors
+    /// Returns [`CodecError::UnexpectedEnd`] if `bytes` ends inside a varint,
+    /// [`CodecError::InvalidVarint`] for malformed varints and
+    /// [`CodecError::OutOfRange`] if an index lies beyond `BIT_COUNT`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, CodecError, bucket_count};
+    ///
+    /// type Bm = BitMap<10, { bucket_count(10) }>;
+    /// assert_eq!(Bm::decode_index_list(&[1, 2]), Ok(Bm::from_ones_iter([1, 4])));
+    /// assert_eq!(Bm::decode_index_list(&[1, 8]), Err(CodecError::OutOfRange));
+    /// ```
+    ///
+    /// [`encode_index_list`]: BitMap::encode_index_list
+    pub fn decode_index_list(bytes: &[u8]) -> Result<Self, CodecError> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        let mut bitmap = Self::new();
+        let mut varint = VarintReader::default();
+        let mut next = 0;
+        for &byte in bytes {
+            if let Some(gap) = varint.push(byte)? {
+                let idx = usize::checked_add(next, gap)
+                    .filter(|&idx| idx < BIT_COUNT)
+                    .ok_or(CodecError::OutOfRange)?;
+                bitmap.set(idx);
+                next = idx + 1;
+            }
+        }
+        if varint.is_partial() {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        Ok(bitmap)
+    }
+
+    /// Returns the maximum number of bytes [`encode_compact`] writes for any
+    /// bitmap of this size.
+    ///
+    /// Usable in const contexts, e.g. to size a buffer on the stack.
+    ///
+    /// [`encode_compact`]: BitMap::encode_compact
+    #[inline]
+    pub const fn max_compact_len() -> usize {
+        // the raw buckets are always a candidate
+        BUCKET_COUNT + 1
+    }
+
+    /// Encodes the bitmap in whichever of four encodings is the smallest for
+    /// its contents and returns the number of bytes written.
+    ///
+    /// The first byte is a tag naming the encoding of the remaining bytes:
+    ///
+    /// - `0`: the raw buckets, bit `i` in bit `i % 8` of byte `i / 8`
+    /// - `1`: the indices of the set bits as written by
+    ///   [`encode_index_list`], best for sparse bitmaps
+    /// - `2`: the indices of the unset bits in the same format, best for
+    ///   nearly full bitmaps
+    /// - `3`: the run lengths as written by [`encode_rle`], best for bitmaps
+    ///   of few long runs
+    ///
+    /// The [`popcount`] rules out index lists that cannot beat the raw
+    /// buckets before their size is computed. On a tie the lower tag wins.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::BufferTooSmall`] if `out` is too small. A buffer
+    /// of [`max_compact_len`] bytes always suffices.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<1000, { bucket_count(1000) }>;
+    /// let mut buf = [0u8; Bm::max_compact_len()];
+    ///
+    /// let sparse = Bm::from_ones_iter([3, 4, 300]);
+    /// let len = sparse.encode_compact(&mut buf).unwrap();
+    /// assert_eq!(&buf[..len], &[1, 3, 0, 0xa7, 0x02]);
+    /// assert_eq!(Bm::decode_compact(&buf[..len]), Ok(sparse));
+    ///
+    /// let mut runs = Bm::new();
+    /// runs.set_range(100..900);
+    /// let len = runs.encode_compact(&mut buf).unwrap();
+    /// assert_eq!(buf[0], 3);
+    /// assert_eq!(Bm::decode_compact(&buf[..len]), Ok(runs));
+    /// ```
+    ///
+    /// [`encode_index_list`]: BitMap::encode_index_list
+    /// [`encode_rle`]: BitMap::encode_rle
+    /// [`popcount`]: BitMap::popcount
+    /// [`max_compact_len`]: BitMap::max_compact_len
+    pub fn encode_compact(&self, out: &mut [u8]) -> Result<usize, CodecError> {
+        let ones = self.popcount();
+        let zeros = BIT_COUNT - ones;
+        let mut best = (TAG_RAW, BUCKET_COUNT);
+        // every listed index takes at least one byte
+        if ones < best.1 {
+            let len = indices_len(self.iter_ones());
+            if len < best.1 {
+                best = (TAG_INDEX_LIST, len);
+            }
+        }
+        if zeros < best.1 {
+            let len = indices_len(self.iter_zeros());
+            if len < best.1 {
+                best = (TAG_INVERTED_INDEX_LIST, len);
+            }
+        }
+        let rle_len = self.rle_len();
+        if rle_len < best.1 {
+            best = (TAG_RLE, rle_len);
+        }
+
+        let (tag, body) = out.split_first_mut().ok_or(CodecError::BufferTooSmall)?;
+        *tag = best.0;
+        let len = match best.0 {
+            TAG_RAW => {
+                let raw = body
+                    .get_mut(..BUCKET_COUNT)
+                    .ok_or(CodecError::BufferTooSmall)?;
+                raw.copy_from_slice(&self.0);
+                BUCKET_COUNT
+            }
+            TAG_INDEX_LIST => encode_indices(self.iter_ones(), body)?,
+            TAG_INVERTED_INDEX_LIST => encode_indices(self.iter_zeros(), body)?,
+            _ => self.encode_rle(body)?,
+        };
+        Ok(1 + len)
+    }
+
+    /// Decodes a bitmap written by [`encode_compact`], reading the encoding
+    /// from the leading tag byte.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::InvalidTag`] for an unknown tag,
+    /// [`CodecError::UnexpectedEnd`] for empty input and any error of the
+    /// tagged encoding otherwise. Raw buckets fail with
+    /// [`CodecError::UnexpectedEnd`] or [`CodecError::TrailingBytes`] if they
+    /// are not exactly `BUCKET_COUNT` bytes and with
+    /// [`CodecError::OutOfRange`] if they set bits beyond `BIT_COUNT`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, CodecError, bucket_count};
+    ///
+    /// type Bm = BitMap<10, { bucket_count(10) }>;
+    /// assert_eq!(Bm::decode_compact(&[0, 0x12, 0x02]), Ok(Bm::from_ones_iter([1, 4, 9])));
+    /// assert_eq!(Bm::decode_compact(&[2, 0]), Ok(Bm::from_ones_iter(1..10)));
+    /// assert_eq!(Bm::decode_compact(&[7]), Err(CodecError::InvalidTag));
+    /// ```
+    ///
+    /// [`encode_compact`]: BitMap::encode_compact
+    pub fn decode_compact(bytes: &[u8]) -> Result<Self, CodecError> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        let (&tag, body) = bytes.split_first().ok_or(CodecError::UnexpectedEnd)?;
+        match tag {
+            TAG_RAW => {
+                if body.len() < BUCKET_COUNT {
+                    return Err(CodecError::UnexpectedEnd);
+                }
+                if body.len() > BUCKET_COUNT {
+                    return Err(CodecError::TrailingBytes);
+                }
+                let mut bitmap = Self::new();
+                bitmap.0.copy_from_slice(body);
+                if !bitmap.has_clean_unused_bits() {
+                    return Err(CodecError::OutOfRange);
+                }
+                Ok(bitmap)
+            }
+            TAG_INDEX_LIST => Self::decode_index_list(body),
+            TAG_INVERTED_INDEX_LIST => Self::decode_index_list(body).map(|bitmap| !bitmap),
+            TAG_RLE => Self::decode_rle(body),
+            _ => Err(CodecError::InvalidTag),
+        }
+    }
+}
+
+/// Writes the gaps between ascending `indices` as varints.
+fn encode_indices<I: Iterator<Item = usize>>(
+    indices: I,
+    out: &mut [u8],
+) -> Result<usize, CodecError> {
+    let mut written = 0;
+    let mut next = 0;
+    for idx in indices {
+        write_varint(out, &mut written, idx - next)?;
+        next = idx + 1;
+    }
+    Ok(written)
+}
+
+/// Returns the number of bytes [`encode_indices`] writes for `indices`.
+fn indices_len<I: Iterator<Item = usize>>(indices: I) -> usize {
+    let mut len = 0;
+    let mut next = 0;
+    for idx in indices {
+        len += varint_len(idx - next);
+        next = idx + 1;
+    }
+    len
+}
@@ -0,0 +1,98 @@
+use crate::bitmap::BitMap;
+use core::ops::Range;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Copies the bits in `src` to the equally long range starting at `dest`,
+    /// like [`slice::copy_within`].
+    ///
+    /// The ranges may overlap. Moves up to 64 bits at a time instead of single
+    /// bits, shifting and masking whole buckets for unaligned offsets.
+    ///
+    /// # Panics
+    /// Panics if `src` or the destination range reach beyond `BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 2, 3]);
+    /// bm.copy_within(0..4, 3);
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 2, 3, 5, 6]));
+    /// ```
+    pub fn copy_within(&mut self, src: Range<usize>, dest: usize) {
+        let len = src.len();
+        assert!(src.end <= BIT_COUNT, "Range end {} out of bounds", src.end);
+        assert_dest_in_bounds::<BIT_COUNT>(dest, len);
+
+        if dest <= src.start {
+            let mut offset = 0;
+            while offset < len {
+                let chunk = (len - offset).min(64);
+                let bits = self.read_bits(src.start + offset, chunk);
+                self.write_bits(dest + offset, chunk, bits);
+                offset += chunk;
+            }
+        } else {
+            // copies from the top down so no source bit is overwritten before
+            // it has been read
+            let mut offset = len;
+            while offset > 0 {
+                let chunk = offset.min(64);
+                offset -= chunk;
+                let bits = self.read_bits(src.start + offset, chunk);
+                self.write_bits(dest + offset, chunk, bits);
+            }
+        }
+    }
+
+    /// Copies the bits in `src` of `other` to the equally long range starting
+    /// at `dest`.
+    ///
+    /// `other` may have a different size. Moves up to 64 bits at a time
+    /// instead of single bits, shifting and masking whole buckets for
+    /// unaligned offsets.
+    ///
+    /// # Panics
+    /// Panics if `src` reaches beyond `OTHER_BIT_COUNT` or the destination
+    /// range reaches beyond `BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let header = BitMap::<4, { bucket_count(4) }>::from_ones_iter([0, 3]);
+    /// let mut frame = BitMap::<32, { bucket_count(32) }>::new();
+    /// frame.copy_range_from(&header, 0..4, 13);
+    /// assert_eq!(frame, BitMap::from_ones_iter([13, 16]));
+    /// ```
+    pub fn copy_range_from<const OTHER_BIT_COUNT: usize, const OTHER_BUCKET_COUNT: usize>(
+        &mut self,
+        other: &BitMap<OTHER_BIT_COUNT, OTHER_BUCKET_COUNT>,
+        src: Range<usize>,
+        dest: usize,
+    ) {
+        let len = src.len();
+        assert!(
+            src.end <= OTHER_BIT_COUNT,
+            "Range end {} out of bounds",
+            src.end
+        );
+        assert_dest_in_bounds::<BIT_COUNT>(dest, len);
+
+        let mut offset = 0;
+        while offset < len {
+            let chunk = (len - offset).min(64);
+            let bits = other.read_bits(src.start + offset, chunk);
+            self.write_bits(dest + offset, chunk, bits);
+            offset += chunk;
+        }
+    }
+}
+
+fn assert_dest_in_bounds<const BIT_COUNT: usize>(dest: usize, len: usize) {
+    assert!(
+        dest + len <= BIT_COUNT,
+        "Destination end {} out of bounds",
+        dest + len
+    );
+}
@@ -0,0 +1,24 @@
+use crate::bitmap::BitMap;
+use core::array::from_fn;
+use defmt::{Format, Formatter};
+
+/// Logs the buckets as raw bytes together with `BIT_COUNT` in a single
+/// write.
+///
+/// Every bucket is sent with its bits reversed, so the host decoder renders
+/// the bits from least to most significant like [`Debug`](core::fmt::Debug)
+/// does. defmt display hints cannot reproduce the exact `Debug` layout
+/// though: the host shows a list of 8 bit groups without their offsets, e.g.
+/// `LSB -> [10100001, 00000000] <- MSB (9 bits)`, and the unused bits of the
+/// last bucket show as trailing zeros.
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Format for BitMap<BIT_COUNT, BUCKET_COUNT> {
+    fn format(&self, f: Formatter<'_>) {
+        let lsb_first: [u8; BUCKET_COUNT] = from_fn(|i| self.0[i].reverse_bits());
+        defmt::write!(
+            f,
+            "LSB -> {=[u8]:08b} <- MSB ({=usize} bits)",
+            &lsb_first[..],
+            BIT_COUNT
+        );
+    }
+}
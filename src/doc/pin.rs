@@ -0,0 +1,25 @@
+// Shared setup for the `EnumSet` doc examples, pulled in with a hidden
+// `include!`.
+
+pub use light_bitmap::{BitIndexed, EnumSet, bucket_count};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pin {
+    A,
+    B,
+    C,
+}
+
+impl BitIndexed for Pin {
+    const VARIANT_COUNT: usize = 3;
+
+    fn to_index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(idx: usize) -> Option<Self> {
+        [Pin::A, Pin::B, Pin::C].get(idx).copied()
+    }
+}
+
+pub type Pins = EnumSet<Pin, { Pin::VARIANT_COUNT }, { bucket_count(Pin::VARIANT_COUNT) }>;
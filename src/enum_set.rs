@@ -0,0 +1,673 @@
+use crate::bitmap::{BitMap, IterOnes};
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Iterator};
+use core::marker::PhantomData;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+
+/// Maps the variants of a fieldless enum to bit indices.
+///
+/// Implementors must map every variant to a unique index in
+/// `0..VARIANT_COUNT` and [`from_index`] must be the inverse of [`to_index`].
+///
+/// With the `derive` feature enabled this trait can be derived for fieldless
+/// enums, numbering the variants in declaration order.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::BitIndexed;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Pin {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// impl BitIndexed for Pin {
+///     const VARIANT_COUNT: usize = 3;
+///
+///     fn to_index(self) -> usize {
+///         self as usize
+///     }
+///
+///     fn from_index(idx: usize) -> Option<Self> {
+///         [Pin::A, Pin::B, Pin::C].get(idx).copied()
+///     }
+/// }
+///
+/// assert_eq!(Pin::C.to_index(), 2);
+/// assert_eq!(Pin::from_index(1), Some(Pin::B));
+/// assert_eq!(Pin::from_index(3), None);
+/// ```
+///
+/// [`from_index`]: BitIndexed::from_index
+/// [`to_index`]: BitIndexed::to_index
+pub trait BitIndexed: Copy {
+    /// The number of variants, which is also the number of bits needed to
+    /// store a set of them.
+    const VARIANT_COUNT: usize;
+
+    /// Returns the bit index of this variant.
+    fn to_index(self) -> usize;
+
+    /// Returns the variant with the given bit index or `None` if
+    /// `idx >= VARIANT_COUNT`.
+    fn from_index(idx: usize) -> Option<Self>;
+}
+
+#[allow(clippy::no_effect)]
+#[allow(clippy::unnecessary_operation)]
+const fn compile_assert_variant_count(variant_count: usize, bit_count: usize) {
+    // This will cause a compile-time error if variant_count != bit_count
+    ["BIT_COUNT must match BitIndexed::VARIANT_COUNT."][(variant_count != bit_count) as usize];
+}
+
+fn runtime_assert_variant_count(variant_count: usize, bit_count: usize) {
+    assert_eq!(
+        variant_count, bit_count,
+        "BIT_COUNT must match BitIndexed::VARIANT_COUNT."
+    );
+}
+
+/// A set of enum variants backed by a [`BitMap`].
+///
+/// `BIT_COUNT` must equal [`BitIndexed::VARIANT_COUNT`] of `E` and
+/// `BUCKET_COUNT` should be set via const expression with
+/// [`bucket_count`](crate::bucket_count). Defining a type alias keeps the
+/// signature readable.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitIndexed, EnumSet, bucket_count};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Pin {
+///     A,
+///     B,
+///     C,
+/// }
+/// # impl BitIndexed for Pin {
+/// #     const VARIANT_COUNT: usize = 3;
+/// #     fn to_index(self) -> usize { self as usize }
+/// #     fn from_index(idx: usize) -> Option<Self> {
+/// #         [Pin::A, Pin::B, Pin::C].get(idx).copied()
+/// #     }
+/// # }
+///
+/// type Pins = EnumSet<Pin, { Pin::VARIANT_COUNT }, { bucket_count(Pin::VARIANT_COUNT) }>;
+///
+/// let mut pins = Pins::new();
+/// pins.insert(Pin::C);
+/// pins.insert(Pin::A);
+/// assert!(pins.contains(Pin::A));
+/// assert!(!pins.contains(Pin::B));
+/// let mut iter = pins.iter();
+/// assert_eq!(iter.next(), Some(Pin::A));
+/// assert_eq!(iter.next(), Some(Pin::C));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub struct EnumSet<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bits: BitMap<BIT_COUNT, BUCKET_COUNT>,
+    variants: PhantomData<E>,
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize>
+    EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    /// Creates a new, empty set.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT != E::VARIANT_COUNT`, `BIT_COUNT == 0` or
+    /// `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let pins = Pins::new();
+    /// assert!(pins.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        runtime_assert_variant_count(E::VARIANT_COUNT, BIT_COUNT);
+        Self::from_bitmap(BitMap::new())
+    }
+
+    /// Creates a new, empty `const` set.
+    ///
+    /// Equivalent to [`new`], but callable in compile-time contexts such as
+    /// const initialization.
+    ///
+    /// # Compiler Errors
+    /// Prevents compilation if either `BIT_COUNT != E::VARIANT_COUNT`,
+    /// `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(bit_count)` with an
+    /// unintuitive message like `evaluation of constant value failed` and
+    /// `index out of bounds: the length is 1 but the index is 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// const NONE: Pins = EnumSet::const_empty();
+    /// assert!(NONE.is_empty());
+    /// ```
+    ///
+    /// [`new`]: EnumSet::new
+    pub const fn const_empty() -> Self {
+        compile_assert_variant_count(E::VARIANT_COUNT, BIT_COUNT);
+        Self {
+            bits: BitMap::const_empty(),
+            variants: PhantomData,
+        }
+    }
+
+    /// Creates a new set containing every variant.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT != E::VARIANT_COUNT`, `BIT_COUNT == 0` or
+    /// `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let pins = Pins::all();
+    /// assert_eq!(pins.len(), 3);
+    /// ```
+    pub fn all() -> Self {
+        runtime_assert_variant_count(E::VARIANT_COUNT, BIT_COUNT);
+        Self::from_bitmap(BitMap::with_all_set())
+    }
+
+    /// Wraps a bitmap whose bit `i` stands for the variant with index `i`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT != E::VARIANT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// use light_bitmap::BitMap;
+    ///
+    /// let pins = Pins::from_bitmap(BitMap::from_ones_iter([1]));
+    /// assert!(pins.contains(Pin::B));
+    /// ```
+    #[inline]
+    pub fn from_bitmap(bits: BitMap<BIT_COUNT, BUCKET_COUNT>) -> Self {
+        runtime_assert_variant_count(E::VARIANT_COUNT, BIT_COUNT);
+        Self {
+            bits,
+            variants: PhantomData,
+        }
+    }
+
+    /// Returns the underlying bitmap.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::new();
+    /// pins.insert(Pin::C);
+    /// assert!(pins.as_bitmap().is_set(2));
+    /// ```
+    #[inline]
+    pub fn as_bitmap(&self) -> &BitMap<BIT_COUNT, BUCKET_COUNT> {
+        &self.bits
+    }
+
+    /// Adds a variant to the set.
+    ///
+    /// Returns `true` if the variant wasn't present before.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::new();
+    /// assert!(pins.insert(Pin::B));
+    /// assert!(!pins.insert(Pin::B));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, variant: E) -> bool {
+        !self.bits.test_and_set(variant.to_index())
+    }
+
+    /// Removes a variant from the set.
+    ///
+    /// Returns `true` if the variant was present before.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::all();
+    /// assert!(pins.remove(Pin::A));
+    /// assert!(!pins.remove(Pin::A));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, variant: E) -> bool {
+        self.bits.test_and_unset(variant.to_index())
+    }
+
+    /// Returns `true` if the set contains the variant.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::new();
+    /// pins.insert(Pin::A);
+    /// assert!(pins.contains(Pin::A));
+    /// assert!(!pins.contains(Pin::C));
+    /// ```
+    #[inline]
+    pub fn contains(&self, variant: E) -> bool {
+        self.bits.is_set(variant.to_index())
+    }
+
+    /// Removes all variants from the set.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::all();
+    /// pins.clear();
+    /// assert!(pins.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits.unset_range(0..BIT_COUNT);
+    }
+
+    /// Returns the number of variants in the set.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let pins: Pins = [Pin::A, Pin::C].into_iter().collect();
+    /// assert_eq!(pins.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.popcount()
+    }
+
+    /// Returns `true` if the set contains no variants.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let mut pins = Pins::new();
+    /// assert!(pins.is_empty());
+    /// pins.insert(Pin::B);
+    /// assert!(!pins.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits.first_set_bit().is_none()
+    }
+
+    /// Returns an iterator over the variants in the set, in index order.
+    ///
+    /// # Panics
+    /// The iterator panics if [`BitIndexed::from_index`] returns `None` for
+    /// an index below `VARIANT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let pins: Pins = [Pin::C, Pin::B].into_iter().collect();
+    /// let mut iter = pins.iter();
+    /// assert_eq!(iter.next(), Some(Pin::B));
+    /// assert_eq!(iter.next(), Some(Pin::C));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> EnumSetIter<'_, E, BIT_COUNT, BUCKET_COUNT> {
+        EnumSetIter {
+            ones: self.bits.iter_ones(),
+            variants: PhantomData,
+        }
+    }
+
+    /// Returns a new set containing the variants that are in `self` or in
+    /// `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A].into_iter().collect();
+    /// let b: Pins = [Pin::C].into_iter().collect();
+    /// assert_eq!(a.union(&b), [Pin::A, Pin::C].into_iter().collect());
+    /// ```
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_bitmap(self.bits.bit_or(&other.bits))
+    }
+
+    /// Returns a new set containing the variants that are in both `self` and
+    /// `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A, Pin::B].into_iter().collect();
+    /// let b: Pins = [Pin::B, Pin::C].into_iter().collect();
+    /// assert_eq!(a.intersection(&b), [Pin::B].into_iter().collect());
+    /// ```
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::from_bitmap(self.bits.bit_and(&other.bits))
+    }
+
+    /// Returns a new set containing the variants that are in `self` but not in
+    /// `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A, Pin::B].into_iter().collect();
+    /// let b: Pins = [Pin::B, Pin::C].into_iter().collect();
+    /// assert_eq!(a.difference(&b), [Pin::A].into_iter().collect());
+    /// ```
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self::from_bitmap(self.bits.bit_and_not(&other.bits))
+    }
+
+    /// Returns a new set containing the variants that are in exactly one of
+    /// `self` and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A, Pin::B].into_iter().collect();
+    /// let b: Pins = [Pin::B, Pin::C].into_iter().collect();
+    /// assert_eq!(a.symmetric_difference(&b), [Pin::A, Pin::C].into_iter().collect());
+    /// ```
+    #[inline]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Self::from_bitmap(self.bits.bit_xor(&other.bits))
+    }
+
+    /// Returns a new set containing all variants that are not in `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::B].into_iter().collect();
+    /// assert_eq!(a.complement(), [Pin::A, Pin::C].into_iter().collect());
+    /// ```
+    #[inline]
+    pub fn complement(&self) -> Self {
+        Self::from_bitmap(self.bits.bit_not())
+    }
+
+    /// Returns `true` if every variant in `self` is also in `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::B].into_iter().collect();
+    /// let b: Pins = [Pin::A, Pin::B].into_iter().collect();
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    /// ```
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits.bit_and(&other.bits) == self.bits
+    }
+
+    /// Returns `true` if every variant in `other` is also in `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A, Pin::B].into_iter().collect();
+    /// let b: Pins = [Pin::B].into_iter().collect();
+    /// assert!(a.is_superset(&b));
+    /// assert!(!b.is_superset(&a));
+    /// ```
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no variants in common.
+    ///
+    /// # Examples
+    /// ```
+    /// # mod pin { include!("doc/pin.rs"); }
+    /// # use pin::*;
+    /// let a: Pins = [Pin::A].into_iter().collect();
+    /// let b: Pins = [Pin::B, Pin::C].into_iter().collect();
+    /// assert!(a.is_disjoint(&b));
+    /// ```
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits.bit_and(&other.bits).first_set_bit().is_none()
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Clone
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Copy
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> PartialEq
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Eq
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Hash
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Default
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: BitIndexed + Debug, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Debug
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'set, E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> IntoIterator
+    for &'set EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = E;
+    type IntoIter = EnumSetIter<'set, E, BIT_COUNT, BUCKET_COUNT>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Constructs a set from an iterator over variants.
+///
+/// # Panics
+/// Panics if `BIT_COUNT != E::VARIANT_COUNT`, `BIT_COUNT == 0` or
+/// `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> FromIterator<E>
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn from_iter<T: IntoIterator<Item = E>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Extend<E>
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn extend<T: IntoIterator<Item = E>>(&mut self, iter: T) {
+        for variant in iter {
+            self.insert(variant);
+        }
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitAnd
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitAndAssign
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.bits.in_place_bit_and(&rhs.bits)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitOr
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitOrAssign
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.bits.in_place_bit_or(&rhs.bits)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitXor
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.symmetric_difference(&rhs)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitXorAssign
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.bits.in_place_bit_xor(&rhs.bits)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Sub
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> SubAssign
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.bits.in_place_bit_and_not(&rhs.bits)
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Not
+    for EnumSet<E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+/// Iterator over the variants in an [`EnumSet`].
+///
+/// Yields the variants in ascending index order.
+///
+/// Returned by [`EnumSet::iter()`].
+pub struct EnumSetIter<'set, E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    ones: IterOnes<'set, BIT_COUNT, BUCKET_COUNT>,
+    variants: PhantomData<E>,
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Clone
+    for EnumSetIter<'_, E, BIT_COUNT, BUCKET_COUNT>
+{
+    fn clone(&self) -> Self {
+        Self {
+            ones: self.ones,
+            variants: PhantomData,
+        }
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for EnumSetIter<'_, E, BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = E;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.ones.next()?;
+        Some(
+            E::from_index(idx)
+                .expect("BitIndexed::from_index must accept every index below VARIANT_COUNT"),
+        )
+    }
+}
+
+impl<E: BitIndexed, const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for EnumSetIter<'_, E, BIT_COUNT, BUCKET_COUNT>
+{
+}
@@ -0,0 +1,104 @@
+use crate::bitmap::BitMap;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Gathers the bits of `self` selected by `mask` into the low bits of the
+    /// result (parallel bit extract, PEXT).
+    ///
+    /// The bit at the `k`-th set index of `mask` becomes bit `k` of the
+    /// result, all higher bits of the result are unset. Works bucket by bucket
+    /// and is usable in const contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<12, { bucket_count(12) }>;
+    /// let bm = Bm::from_ones_iter([1, 4, 9, 11]);
+    /// let mask = Bm::from_ones_iter([0, 1, 9, 10, 11]);
+    /// assert_eq!(bm.extract(&mask), Bm::from_ones_iter([1, 2, 4]));
+    /// ```
+    pub const fn extract(&self, mask: &Self) -> Self {
+        let mut result = Self([0u8; BUCKET_COUNT]);
+        let mut out_pos = 0;
+        let mut i = 0;
+        while i < BUCKET_COUNT {
+            let mask_byte = mask.0[i];
+            let bits = pext_u8(self.0[i], mask_byte);
+            let (byte_idx, bit_idx) = (out_pos / 8, out_pos % 8);
+            result.0[byte_idx] |= bits << bit_idx;
+            if bit_idx != 0 && byte_idx + 1 < BUCKET_COUNT {
+                result.0[byte_idx + 1] |= bits >> (8 - bit_idx);
+            }
+            out_pos += mask_byte.count_ones() as usize;
+            i += 1;
+        }
+        result
+    }
+
+    /// Scatters the low bits of `self` to the set indices of `mask` (parallel
+    /// bit deposit, PDEP).
+    ///
+    /// Bit `k` of `self` is moved to the `k`-th set index of `mask`, all
+    /// indices not set in `mask` are unset in the result. Inverse of
+    /// [`extract`] for the bits selected by `mask`. Works bucket by bucket and
+    /// is usable in const contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<12, { bucket_count(12) }>;
+    /// let packed = Bm::from_ones_iter([1, 2, 4]);
+    /// let mask = Bm::from_ones_iter([0, 1, 9, 10, 11]);
+    /// assert_eq!(packed.deposit(&mask), Bm::from_ones_iter([1, 9, 11]));
+    /// ```
+    ///
+    /// [`extract`]: BitMap::extract
+    pub const fn deposit(&self, mask: &Self) -> Self {
+        let mut result = Self([0u8; BUCKET_COUNT]);
+        let mut in_pos = 0;
+        let mut i = 0;
+        while i < BUCKET_COUNT {
+            let mask_byte = mask.0[i];
+            let (byte_idx, bit_idx) = (in_pos / 8, in_pos % 8);
+            let mut bits = self.0[byte_idx] >> bit_idx;
+            if bit_idx != 0 && byte_idx + 1 < BUCKET_COUNT {
+                bits |= self.0[byte_idx + 1] << (8 - bit_idx);
+            }
+            result.0[i] = pdep_u8(bits, mask_byte);
+            in_pos += mask_byte.count_ones() as usize;
+            i += 1;
+        }
+        result
+    }
+}
+
+/// Gathers the bits of `value` selected by `mask` into the low bits.
+const fn pext_u8(value: u8, mut mask: u8) -> u8 {
+    let mut result = 0;
+    let mut out_bit = 0;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if value & lowest != 0 {
+            result |= 1 << out_bit;
+        }
+        out_bit += 1;
+        mask &= mask - 1;
+    }
+    result
+}
+
+/// Scatters the low bits of `value` to the set bits of `mask`.
+const fn pdep_u8(value: u8, mut mask: u8) -> u8 {
+    let mut result = 0;
+    let mut in_bit = 0;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if value & 1 << in_bit != 0 {
+            result |= lowest;
+        }
+        in_bit += 1;
+        mask &= mask - 1;
+    }
+    result
+}
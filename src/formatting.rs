@@ -0,0 +1,314 @@
+use crate::bitmap::BitMap;
+use core::fmt::{Alignment, Binary, Display, Formatter, LowerHex, Octal, UpperHex, Write};
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns a wrapper that formats the bitmap like the bitmap itself but
+    /// inserts `separator` between every `group_size` digits, counted from
+    /// the least significant digit.
+    ///
+    /// Supports [`Display`], [`Binary`], [`Octal`], [`LowerHex`] and
+    /// [`UpperHex`] with the same flags as the bitmap. Separators count
+    /// towards the width.
+    ///
+    /// # Panics
+    /// Panics if `group_size == 0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 5, 11]);
+    /// assert_eq!(format!("{}", bm.grouped(4, '_')), "1000_0010_0001");
+    /// assert_eq!(format!("{:#x}", bm.grouped(2, ' ')), "0x8 21");
+    /// ```
+    #[inline]
+    pub fn grouped(
+        &self,
+        group_size: usize,
+        separator: char,
+    ) -> Grouped<'_, BIT_COUNT, BUCKET_COUNT> {
+        assert_ne!(group_size, 0, "Group size must be greater than zero.");
+        Grouped {
+            bitmap: self,
+            group_size,
+            separator,
+        }
+    }
+
+    /// Writes the bitmap as a number in the given radix, honoring the width,
+    /// fill, alignment, `0` and `#` flags of `f` like the integer formatting
+    /// traits do.
+    fn fmt_radix(
+        &self,
+        f: &mut Formatter<'_>,
+        radix: Radix,
+        full_width: bool,
+        grouping: Option<(usize, char)>,
+    ) -> core::fmt::Result {
+        let bits_per_digit = radix.bits_per_digit();
+        let digit_count = if full_width {
+            BIT_COUNT.div_ceil(bits_per_digit)
+        } else {
+            // at least one digit, so that an empty bitmap is written as 0
+            self.significant_bits().div_ceil(bits_per_digit).max(1)
+        };
+        let separator_count = grouping.map_or(0, |(size, _)| (digit_count - 1) / size);
+        let prefix = if f.alternate() { radix.prefix() } else { "" };
+        let len = prefix.len() + digit_count + separator_count;
+
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+        if f.sign_aware_zero_pad() {
+            f.write_str(prefix)?;
+            for _ in 0..padding {
+                f.write_char('0')?;
+            }
+            return self.write_digits(f, radix, digit_count, grouping);
+        }
+
+        let (pre_padding, post_padding) = match f.align() {
+            Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Center) => (padding / 2, padding.div_ceil(2)),
+            Some(Alignment::Right) | None => (padding, 0),
+        };
+        let fill = f.fill();
+        for _ in 0..pre_padding {
+            f.write_char(fill)?;
+        }
+        f.write_str(prefix)?;
+        self.write_digits(f, radix, digit_count, grouping)?;
+        for _ in 0..post_padding {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+
+    fn write_digits(
+        &self,
+        f: &mut Formatter<'_>,
+        radix: Radix,
+        digit_count: usize,
+        grouping: Option<(usize, char)>,
+    ) -> core::fmt::Result {
+        let bits_per_digit = radix.bits_per_digit();
+        for digit_idx in (0..digit_count).rev() {
+            let digit = (0..bits_per_digit).fold(0, |digit, bit| {
+                let idx = digit_idx * bits_per_digit + bit;
+                let is_set = idx < BIT_COUNT && self.0[idx / 8] & 1 << (idx % 8) != 0;
+                digit | (is_set as u32) << bit
+            });
+            f.write_char(radix.digit_char(digit))?;
+            if let Some((size, separator)) = grouping
+                && digit_idx != 0
+                && digit_idx % size == 0
+            {
+                f.write_char(separator)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the highest set bit plus one, or zero if all bits
+    /// are unset.
+    fn significant_bits(&self) -> usize {
+        self.0
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |byte_idx| {
+                byte_idx * 8 + 8 - self.0[byte_idx].leading_zeros() as usize
+            })
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Radix {
+    Binary,
+    Octal,
+    LowerHex,
+    UpperHex,
+}
+
+impl Radix {
+    fn bits_per_digit(self) -> usize {
+        match self {
+            Radix::Binary => 1,
+            Radix::Octal => 3,
+            Radix::LowerHex | Radix::UpperHex => 4,
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::LowerHex | Radix::UpperHex => "0x",
+        }
+    }
+
+    fn digit_char(self, digit: u32) -> char {
+        let c = char::from_digit(digit, 16).unwrap_or('?');
+        match self {
+            Radix::UpperHex => c.to_ascii_uppercase(),
+            _ => c,
+        }
+    }
+}
+
+/// Formats all `BIT_COUNT` bits as binary digits, most significant bit first,
+/// including leading zeros.
+///
+/// Honors width, fill and alignment. The `#` flag adds a `0b` prefix.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm = BitMap::<6, { bucket_count(6) }>::from_ones_iter([0, 2]);
+/// assert_eq!(format!("{bm}"), "000101");
+/// assert_eq!(format!("{bm:#}"), "0b000101");
+/// assert_eq!(format!("{bm:>8}"), "  000101");
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Display
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Binary, true, None)
+    }
+}
+
+/// Formats the bitmap as an unsigned binary number, most significant bit
+/// first and without leading zeros, like `{:b}` does for integers.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 2]);
+/// assert_eq!(format!("{bm:b}"), "101");
+/// assert_eq!(format!("{bm:#010b}"), "0b00000101");
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Binary for BitMap<BIT_COUNT, BUCKET_COUNT> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Binary, false, None)
+    }
+}
+
+/// Formats the bitmap as an unsigned octal number without leading zeros, like
+/// `{:o}` does for integers.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 3, 11]);
+/// assert_eq!(format!("{bm:o}"), "4011");
+/// assert_eq!(format!("{bm:#o}"), "0o4011");
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Octal for BitMap<BIT_COUNT, BUCKET_COUNT> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Octal, false, None)
+    }
+}
+
+/// Formats the bitmap as an unsigned lowercase hex number without leading
+/// zeros, like `{:x}` does for integers.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 3, 11]);
+/// assert_eq!(format!("{bm:x}"), "809");
+/// assert_eq!(format!("{bm:#x}"), "0x809");
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> LowerHex
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::LowerHex, false, None)
+    }
+}
+
+/// Formats the bitmap as an unsigned uppercase hex number without leading
+/// zeros, like `{:X}` does for integers.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 3, 11]);
+/// assert_eq!(format!("{bm:X}"), "80B");
+/// assert_eq!(format!("{bm:#06X}"), "0x080B");
+/// ```
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> UpperHex
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::UpperHex, false, None)
+    }
+}
+
+/// Formatting wrapper that groups digits with a separator.
+///
+/// Returned by [`BitMap::grouped()`].
+#[derive(Clone, Copy)]
+pub struct Grouped<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: &'bitmap BitMap<BIT_COUNT, BUCKET_COUNT>,
+    group_size: usize,
+    separator: char,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Grouped<'_, BIT_COUNT, BUCKET_COUNT> {
+    fn fmt_radix(
+        &self,
+        f: &mut Formatter<'_>,
+        radix: Radix,
+        full_width: bool,
+    ) -> core::fmt::Result {
+        self.bitmap.fmt_radix(
+            f,
+            radix,
+            full_width,
+            Some((self.group_size, self.separator)),
+        )
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Display
+    for Grouped<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Binary, true)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Binary
+    for Grouped<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Binary, false)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Octal
+    for Grouped<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::Octal, false)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> LowerHex
+    for Grouped<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::LowerHex, false)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> UpperHex
+    for Grouped<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_radix(f, Radix::UpperHex, false)
+    }
+}
@@ -1,5 +1,6 @@
 //! A minimal, fixed-size bitmap library written in pure Rust.  
-//! `no_std`, no heap / `alloc`, no `unsafe` — just `core`.
+//! `no_std`, no heap / `alloc`, no `unsafe` — just `core`. Only the opt-in
+//! `RoaringBitMap` behind the `alloc` feature allocates.
 //!
 //! Designed for use in embedded and resource-constrained environments.
 //!
@@ -24,8 +25,9 @@
 //! - Embedded development
 //! - Applications that need a compact, stack-only bitmap with no dynamic allocation
 //! - Timing-sensitive systems where allocation unpredictability must be avoided
-//! - Does not support SIMD or parallel execution, so it's not ideal for cases
-//!   where performance needs to be fully maxed out
+//! - Does not support parallel execution and only uses SIMD with the
+//!   nightly-only `simd` feature, so it's not ideal for cases where performance
+//!   needs to be fully maxed out
 //!
 //! # Features
 //!
@@ -38,19 +40,130 @@
 //!   - `iter_ones()` (indices of set bits)
 //!   - `iter_zeros()` (indices of unset bits)
 //! - Support for bitwise ops:
-//!   - `&`, `|`, `^`, `!`
+//!   - `&`, `|`, `^`, `!`, `-` (set difference)
 //!   - `<<`, `>>`
-//!   - `&=`, `|=`, `^=`, `<<=`, `>>=`
-//! - Range operations: `set_range`, `unset_range`
+//!   - `&=`, `|=`, `^=`, `-=`, `<<=`, `>>=`
+//!   - on owned and borrowed operands, e.g. `&a & &b`
+//! - Construction from and extension with iterators of indices or `bool`s
+//! - Folding many bitmaps in one pass: `union_all`, `intersection_all`
+//! - Masked updates: `blend`, `assign_masked`, `set_where`, `clear_where`
+//!   and predicate filtering with `retain`, `retain_ones`
+//! - Range operations: `set_range`, `unset_range`, `copy_within`,
+//!   `copy_range_from`
+//! - Inserting and removing bits with shifting: `insert_bit`, `remove_bit`,
+//!   `insert_range`, `remove_range`
+//! - Test-and-modify returning the previous state: `toggle`, `test_and_set`,
+//!   `test_and_unset`, `replace`, `take`
 //! - Logical operations: `popcount`, `first_set_bit`
 //! - Rotation support: `rotate_left`, `rotate_right`
+//! - Reordering: `reverse_bits`, `swap`, `swap_ranges`, `permute` and
+//!   precomputed `Permutation`s
+//! - Changing the width: `resize`, `try_narrow`, `concat`, `split_at`
+//! - Combinatorial iteration: `subsets`, `subsets_gray`, `combinations`
+//! - Sets of fieldless enum variants: `EnumSet` with the `BitIndexed` trait
+//! - Run operations on consecutive set or unset bits:
+//!   - `iter_runs`, `iter_zero_runs`
+//!   - `longest_run`, `longest_zero_run`
+//!   - `find_run`, `find_zero_run`
+//! - Contiguous range allocation: `alloc_range`, `alloc_range_best_fit`, `free_range`
+//! - Parallel bit extract and deposit (PEXT/PDEP): `extract`, `deposit`
+//! - Bit stream cursors `BitWriter` and `BitReader` for packing unsigned,
+//!   signed and boolean fields in LSB0 or MSB0 order
+//! - Formatting as a number, most significant bit first:
+//!   - `{}` (all bits in binary), `{:b}`, `{:o}`, `{:x}`, `{:X}`
+//!   - `grouped` for digit group separators
+//! - Ordering as unsigned integers with `Ord` and by set inclusion with
+//!   `partial_cmp_subset` or the `SubsetOrd` wrapper
+//! - Run length encoding into and decoding from byte buffers without heap:
+//!   `encode_rle`, `decode_rle` and the streaming `RleDecoder`
+//! - Delta-varint index lists with `encode_index_list`, `decode_index_list`
+//!   and the self-describing `encode_compact`, `decode_compact` picking the
+//!   smallest of raw buckets, index list, inverted index list and run lengths
+//! - Parsing with `FromStr` from binary, octal, hex, the `Debug` layout or
+//!   index lists like `1,3,5-9`
+//!
+//! # Cargo Features
+//!
+//! - `alloc`: `RoaringBitMap`, a compressed set over the whole `u32` range
+//!   storing every chunk of 2^16 values as a sorted array, as a
+//!   `BitMap<65536, 8192>` or as runs, with `insert_bitmap` and
+//!   `extract_bitmap` to move bits between it and fixed-size bitmaps
+//! - `bytemuck`: `Pod` and `Zeroable` for `BitMap`
+//! - `defmt`: `defmt::Format` for `BitMap`, sending the raw buckets and
+//!   `BIT_COUNT` in one write, rendered on the host as 8 bit groups in the
+//!   same bit order as `Debug`
+//! - `derive`: `#[derive(BitIndexed)]` for fieldless enums
+//! - `serde`: `Serialize` and `Deserialize` for `BitMap`, as a binary digit
+//!   string in human-readable formats and as raw bytes otherwise
+//! - `simd` (nightly only): bulk bitwise operations, `popcount`, `first_set_bit`
+//!   and `iter_ones` process 32 buckets at a time with `core::simd`, with
+//!   results identical to the default scalar loops
+//! - `subtle`: `ConstantTimeEq` and `ConditionallySelectable` for `BitMap`,
+//!   plus `popcount_ct`, `is_set_ct` and `blend_ct` whose timing does not
+//!   depend on the contents
+//! - `zerocopy`: `FromBytes`, `IntoBytes`, `KnownLayout` and `Immutable` for
+//!   `BitMap`
+//!
+//! With `bytemuck` or `zerocopy` enabled, `ref_from_bytes_checked` and
+//! `mut_from_bytes_checked` cast bytes to a bitmap in place while rejecting
+//! bytes with bits set beyond `BIT_COUNT`.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 #![no_std]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// lets the derive macros refer to `::light_bitmap` in this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as light_bitmap;
 
+mod bit_stream;
 mod bitmap;
+mod bulk;
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+mod cast;
+mod codec;
+mod compact;
+mod copy;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+mod enum_set;
+mod extract_deposit;
+mod formatting;
+mod masked;
+mod ordering;
+mod parse;
+mod permute;
+mod range_alloc;
+mod resize;
+mod rle;
+#[cfg(feature = "alloc")]
+mod roaring;
+mod runs;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod splice;
+mod subsets;
+#[cfg(feature = "subtle")]
+mod subtle_impl;
 #[cfg(test)]
 mod tests;
 
+pub use bit_stream::{BitOrder, BitReader, BitStreamError, BitWriter};
 pub use bitmap::{BitMap, BitMapIter, IterOnes, IterZeros, bucket_count};
+pub use codec::CodecError;
+pub use enum_set::{BitIndexed, EnumSet, EnumSetIter};
+pub use formatting::Grouped;
+#[cfg(feature = "derive")]
+pub use light_bitmap_derive::BitIndexed;
+pub use ordering::SubsetOrd;
+pub use parse::{ParseBitMapError, ParseErrorKind};
+pub use permute::Permutation;
+pub use rle::RleDecoder;
+#[cfg(feature = "alloc")]
+pub use roaring::{RoaringBitMap, RoaringIter};
+pub use runs::{IterRuns, IterZeroRuns};
+pub use subsets::{Combinations, Subsets, SubsetsGray};
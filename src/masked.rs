@@ -0,0 +1,144 @@
+use crate::bitmap::BitMap;
+use core::array::from_fn;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns a new bitmap taking the bits of `other` where `mask` is set and
+    /// the bits of `self` everywhere else.
+    ///
+    /// Computed bucket by bucket as `self & !mask | other & mask`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<8, { bucket_count(8) }>;
+    /// let a = Bm::from_ones_iter([0, 1, 2, 3]);
+    /// let b = Bm::from_ones_iter([2, 4]);
+    /// let mask = Bm::from_ones_iter([2, 3, 4, 5]);
+    /// assert_eq!(a.blend(&b, &mask), Bm::from_ones_iter([0, 1, 2, 4]));
+    /// ```
+    #[inline]
+    pub fn blend(&self, other: &Self, mask: &Self) -> Self {
+        Self(from_fn(|i| self.0[i] & !mask.0[i] | other.0[i] & mask.0[i]))
+    }
+
+    /// Overwrites the bits of `self` where `mask` is set with those of
+    /// `value`, leaving all other bits unchanged.
+    ///
+    /// In-place version of [`blend`].
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<8, { bucket_count(8) }>;
+    /// let mut register = Bm::from_ones_iter([0, 1, 2, 3]);
+    /// let field_mask = Bm::from_ones_iter([4, 5, 6, 7]);
+    /// let value = Bm::from_ones_iter([5]);
+    /// register.assign_masked(&field_mask, &value);
+    /// assert_eq!(register, Bm::from_ones_iter([0, 1, 2, 3, 5]));
+    /// ```
+    ///
+    /// [`blend`]: BitMap::blend
+    #[inline]
+    pub fn assign_masked(&mut self, mask: &Self, value: &Self) {
+        for ((byte, mask_byte), value_byte) in self.0.iter_mut().zip(mask.0).zip(value.0) {
+            *byte = *byte & !mask_byte | value_byte & mask_byte;
+        }
+    }
+
+    /// Sets every bit that is set in `mask`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::from_ones_iter([0, 1]);
+    /// bm.set_where(&BitMap::from_ones_iter([1, 6]));
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 1, 6]));
+    /// ```
+    #[inline]
+    pub fn set_where(&mut self, mask: &Self) {
+        self.in_place_bit_or(mask);
+    }
+
+    /// Unsets every bit that is set in `mask`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<8, { bucket_count(8) }>::from_ones_iter([0, 1]);
+    /// bm.clear_where(&BitMap::from_ones_iter([1, 6]));
+    /// assert_eq!(bm, BitMap::from_ones_iter([0]));
+    /// ```
+    #[inline]
+    pub fn clear_where(&mut self, mask: &Self) {
+        self.in_place_bit_and_not(mask);
+    }
+
+    /// Unsets every bit whose index `f` returns `false` for.
+    ///
+    /// Calls `f` exactly once for every index in `0..BIT_COUNT`, set or
+    /// unset, in ascending order. Use [`retain_ones`] to only call it for set
+    /// bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 2, 3, 8]);
+    /// let mut calls = 0;
+    /// bm.retain(|idx| {
+    ///     calls += 1;
+    ///     idx % 2 == 0
+    /// });
+    /// assert_eq!(bm, BitMap::from_ones_iter([2, 8]));
+    /// assert_eq!(calls, 10);
+    /// ```
+    ///
+    /// [`retain_ones`]: BitMap::retain_ones
+    pub fn retain<F: FnMut(usize) -> bool>(&mut self, mut f: F) {
+        for (byte_idx, byte) in self.0.iter_mut().enumerate() {
+            let bits_in_byte = (BIT_COUNT - byte_idx * 8).min(8);
+            let mut keep = 0u8;
+            for bit_idx in 0..bits_in_byte {
+                if f(byte_idx * 8 + bit_idx) {
+                    keep |= 1 << bit_idx;
+                }
+            }
+            *byte &= keep;
+        }
+    }
+
+    /// Unsets every set bit whose index `f` returns `false` for.
+    ///
+    /// Calls `f` exactly once for every set bit in ascending order, skipping
+    /// unset bits and empty buckets.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 2, 3, 8]);
+    /// let mut calls = 0;
+    /// bm.retain_ones(|idx| {
+    ///     calls += 1;
+    ///     idx % 2 == 0
+    /// });
+    /// assert_eq!(bm, BitMap::from_ones_iter([2, 8]));
+    /// assert_eq!(calls, 4);
+    /// ```
+    pub fn retain_ones<F: FnMut(usize) -> bool>(&mut self, mut f: F) {
+        for (byte_idx, byte) in self.0.iter_mut().enumerate() {
+            let mut remaining = *byte;
+            while remaining != 0 {
+                let lowest = remaining & remaining.wrapping_neg();
+                if !f(byte_idx * 8 + lowest.trailing_zeros() as usize) {
+                    *byte &= !lowest;
+                }
+                remaining &= remaining - 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::bitmap::BitMap;
+use core::cmp::Ordering;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Compares two bitmaps by set inclusion.
+    ///
+    /// Returns `Less` if `self` is a proper subset of `other`, `Greater` if it
+    /// is a proper superset, `Equal` if both are equal and `None` if neither
+    /// contains the other. Stops at the first bucket that shows the bitmaps
+    /// are incomparable.
+    ///
+    /// This is the partial order used by [`SubsetOrd`], unlike the total
+    /// numeric order of [`Ord`].
+    ///
+    /// # Examples
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let a = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 9]);
+    /// let b = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 2, 9]);
+    /// let c = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0]);
+    /// assert_eq!(a.partial_cmp_subset(&b), Some(Ordering::Less));
+    /// assert_eq!(b.partial_cmp_subset(&a), Some(Ordering::Greater));
+    /// assert_eq!(a.partial_cmp_subset(&a), Some(Ordering::Equal));
+    /// assert_eq!(a.partial_cmp_subset(&c), None);
+    /// ```
+    pub fn partial_cmp_subset(&self, other: &Self) -> Option<Ordering> {
+        let mut self_has_extra = false;
+        let mut other_has_extra = false;
+        for (a, b) in self.0.iter().zip(other.0.iter()) {
+            self_has_extra |= a & !b != 0;
+            other_has_extra |= b & !a != 0;
+            if self_has_extra && other_has_extra {
+                return None;
+            }
+        }
+        Some(match (self_has_extra, other_has_extra) {
+            (false, false) => Ordering::Equal,
+            (false, true) => Ordering::Less,
+            _ => Ordering::Greater,
+        })
+    }
+}
+
+/// Orders bitmaps as unsigned integers with bit `BIT_COUNT - 1` as the most
+/// significant bit, comparing bucket-wise from the highest bucket down.
+///
+/// This is the same order as of the numbers written by the [`Binary`] impl.
+/// For ordering by set inclusion see [`SubsetOrd`].
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, bucket_count};
+///
+/// let low = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 1, 2, 3, 4, 5, 6, 7]);
+/// let high = BitMap::<10, { bucket_count(10) }>::from_ones_iter([8]);
+/// assert!(low < high);
+/// ```
+///
+/// [`Binary`]: core::fmt::Binary
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Ord for BitMap<BIT_COUNT, BUCKET_COUNT> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> PartialOrd
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Wrapper that orders bitmaps by set inclusion.
+///
+/// `a < b` holds if `a` is a proper subset of `b`. Bitmaps where neither
+/// contains the other are incomparable, so this only implements
+/// [`PartialOrd`]. See [`BitMap::partial_cmp_subset()`].
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, SubsetOrd, bucket_count};
+///
+/// let a = SubsetOrd(BitMap::<10, { bucket_count(10) }>::from_ones_iter([9]));
+/// let b = SubsetOrd(BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 9]));
+/// let c = SubsetOrd(BitMap::<10, { bucket_count(10) }>::from_ones_iter([1]));
+/// assert!(a < b);
+/// assert!(!(a < c) && !(a > c) && a != c);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubsetOrd<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    pub BitMap<BIT_COUNT, BUCKET_COUNT>,
+);
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> PartialOrd
+    for SubsetOrd<BIT_COUNT, BUCKET_COUNT>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp_subset(&other.0)
+    }
+}
@@ -0,0 +1,291 @@
+use crate::bitmap::BitMap;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+const DEBUG_PREFIX: &str = "LSB -> ";
+const DEBUG_SUFFIX: &str = " <- MSB";
+
+/// Parses a bitmap from one of the following formats:
+///
+/// - `0b`, `0o` or `0x` followed by binary, octal or hex digits, most
+///   significant digit first, as written by the [`Binary`], [`Octal`],
+///   [`LowerHex`] and [`UpperHex`] impls. Leading zeros are optional.
+/// - Exactly `BIT_COUNT` binary digits without a prefix, as written by the
+///   [`Display`] impl.
+/// - The [`Debug`] layout, e.g. `LSB -> 0: 10100001 8: 0 <- MSB`.
+/// - A comma-separated list of indices and inclusive index ranges, e.g.
+///   `1,3,5-9`. Whitespace around the entries is ignored and an empty string
+///   is an empty bitmap.
+///
+/// Digits may be separated by `_`, so output of [`BitMap::grouped()`] with
+/// `_` as separator parses as well. A string of exactly `BIT_COUNT` binary
+/// digits is always read as binary, never as an index list.
+///
+/// # Errors
+/// Returns a [`ParseBitMapError`] with the byte position and
+/// [`ParseErrorKind`] of the first problem found.
+///
+/// # Panics
+/// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, ParseErrorKind, bucket_count};
+///
+/// type Bm = BitMap<10, { bucket_count(10) }>;
+/// let bm: Bm = "1,3,5-7".parse().unwrap();
+/// assert_eq!(bm, Bm::from_ones_iter([1, 3, 5, 6, 7]));
+/// assert_eq!("0xea".parse::<Bm>(), Ok(bm));
+/// assert_eq!(format!("{bm}").parse::<Bm>(), Ok(bm));
+/// assert_eq!(format!("{bm:?}").parse::<Bm>(), Ok(bm));
+///
+/// let err = "1,3,12".parse::<Bm>().unwrap_err();
+/// assert_eq!(err.position(), 4);
+/// assert_eq!(err.kind(), ParseErrorKind::OutOfRange);
+/// ```
+///
+/// [`Binary`]: core::fmt::Binary
+/// [`Octal`]: core::fmt::Octal
+/// [`LowerHex`]: core::fmt::LowerHex
+/// [`UpperHex`]: core::fmt::UpperHex
+/// [`Debug`]: core::fmt::Debug
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FromStr
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    type Err = ParseBitMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(digits) = s.strip_prefix("0b") {
+            Self::parse_digits(digits, 2, 1)
+        } else if let Some(digits) = s.strip_prefix("0o") {
+            Self::parse_digits(digits, 2, 3)
+        } else if let Some(digits) = s.strip_prefix("0x") {
+            Self::parse_digits(digits, 2, 4)
+        } else if s.starts_with(DEBUG_PREFIX) {
+            Self::parse_debug(s)
+        } else if s.bytes().filter(|byte| *byte != b'_').count() == BIT_COUNT
+            && s.bytes().all(|byte| matches!(byte, b'0' | b'1' | b'_'))
+        {
+            Self::parse_digits(s, 0, 1)
+        } else {
+            Self::parse_index_list(s)
+        }
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Parses digits with `bits_per_digit` bits each, most significant digit
+    /// first. `offset` is the position of `digits` in the whole input.
+    fn parse_digits(
+        digits: &str,
+        offset: usize,
+        bits_per_digit: usize,
+    ) -> Result<Self, ParseBitMapError> {
+        let mut bitmap = Self::new();
+        let digit_count = digits.bytes().filter(|byte| *byte != b'_').count();
+        if digit_count == 0 {
+            return Err(ParseBitMapError::new(offset, ParseErrorKind::Empty));
+        }
+
+        let mut digit_idx = digit_count;
+        for (pos, c) in digits.char_indices() {
+            if c == '_' {
+                continue;
+            }
+            let digit = c
+                .to_digit(1 << bits_per_digit)
+                .ok_or(ParseBitMapError::new(
+                    offset + pos,
+                    ParseErrorKind::InvalidCharacter,
+                ))?;
+            digit_idx -= 1;
+            for bit in 0..bits_per_digit {
+                if digit & 1 << bit == 0 {
+                    continue;
+                }
+                let idx = digit_idx * bits_per_digit + bit;
+                if idx >= BIT_COUNT {
+                    return Err(ParseBitMapError::new(
+                        offset + pos,
+                        ParseErrorKind::OutOfRange,
+                    ));
+                }
+                bitmap.set(idx);
+            }
+        }
+        Ok(bitmap)
+    }
+
+    /// Parses the layout written by the `Debug` impl.
+    fn parse_debug(s: &str) -> Result<Self, ParseBitMapError> {
+        let mut bitmap = Self::new();
+        let mut pos = DEBUG_PREFIX.len();
+        for chunk_start in (0..BIT_COUNT).step_by(8) {
+            if chunk_start != 0 {
+                if !s[pos..].starts_with(' ') {
+                    return Err(debug_mismatch(s, pos));
+                }
+                pos += 1;
+            }
+
+            let label = next_token(s, pos);
+            if label == "<-" {
+                return Err(ParseBitMapError::new(pos, ParseErrorKind::LengthMismatch));
+            }
+            let label_idx = label.strip_suffix(':').and_then(|idx| idx.parse().ok());
+            if label_idx != Some(chunk_start) || !s[pos + label.len()..].starts_with(' ') {
+                return Err(ParseBitMapError::new(pos, ParseErrorKind::InvalidCharacter));
+            }
+            pos += label.len() + 1;
+
+            let bits = next_token(s, pos);
+            for (bit_offset, digit) in bits.char_indices() {
+                match digit {
+                    '1' if chunk_start + bit_offset < BIT_COUNT => {
+                        bitmap.set(chunk_start + bit_offset)
+                    }
+                    '0' | '1' => {}
+                    _ => {
+                        return Err(ParseBitMapError::new(
+                            pos + bit_offset,
+                            ParseErrorKind::InvalidCharacter,
+                        ));
+                    }
+                }
+            }
+            if bits.len() != (BIT_COUNT - chunk_start).min(8) {
+                return Err(ParseBitMapError::new(pos, ParseErrorKind::LengthMismatch));
+            }
+            pos += bits.len();
+        }
+
+        if &s[pos..] != DEBUG_SUFFIX {
+            return Err(debug_mismatch(s, pos));
+        }
+        Ok(bitmap)
+    }
+
+    /// Parses a comma-separated list of indices and inclusive index ranges.
+    fn parse_index_list(s: &str) -> Result<Self, ParseBitMapError> {
+        let mut bitmap = Self::new();
+        if s.trim().is_empty() {
+            return Ok(bitmap);
+        }
+
+        let mut entry_offset = 0;
+        for entry in s.split(',') {
+            let trimmed = entry.trim_start();
+            let pos = entry_offset + entry.len() - trimmed.len();
+            let trimmed = trimmed.trim_end();
+            entry_offset += entry.len() + 1;
+
+            match trimmed.split_once('-') {
+                Some((start, end)) => {
+                    let start_idx = parse_index::<BIT_COUNT>(start, pos)?;
+                    let end_idx = parse_index::<BIT_COUNT>(end, pos + start.len() + 1)?;
+                    if start_idx > end_idx {
+                        return Err(ParseBitMapError::new(pos, ParseErrorKind::InvalidRange));
+                    }
+                    bitmap.set_range(start_idx..end_idx + 1);
+                }
+                None => bitmap.set(parse_index::<BIT_COUNT>(trimmed, pos)?),
+            }
+        }
+        Ok(bitmap)
+    }
+}
+
+/// Returns the part of `s` from `pos` up to the next space.
+fn next_token(s: &str, pos: usize) -> &str {
+    s[pos..].split(' ').next().unwrap_or("")
+}
+
+/// Classifies unexpected input where the `Debug` layout should continue with
+/// the next chunk or end.
+fn debug_mismatch(s: &str, pos: usize) -> ParseBitMapError {
+    let kind = if s.ends_with(DEBUG_SUFFIX) {
+        ParseErrorKind::LengthMismatch
+    } else {
+        ParseErrorKind::InvalidCharacter
+    };
+    ParseBitMapError::new(pos, kind)
+}
+
+/// Parses a decimal bit index starting at `pos` of the whole input.
+fn parse_index<const BIT_COUNT: usize>(
+    digits: &str,
+    pos: usize,
+) -> Result<usize, ParseBitMapError> {
+    if digits.is_empty() {
+        return Err(ParseBitMapError::new(pos, ParseErrorKind::Empty));
+    }
+    if let Some(invalid) = digits.find(|c: char| !c.is_ascii_digit()) {
+        return Err(ParseBitMapError::new(
+            pos + invalid,
+            ParseErrorKind::InvalidCharacter,
+        ));
+    }
+    digits
+        .parse()
+        .ok()
+        .filter(|idx| *idx < BIT_COUNT)
+        .ok_or(ParseBitMapError::new(pos, ParseErrorKind::OutOfRange))
+}
+
+/// Error returned when parsing a [`BitMap`] from a string fails.
+///
+/// Reports the byte position in the input where the problem was found and
+/// its [`ParseErrorKind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseBitMapError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseBitMapError {
+    const fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// Returns the byte position in the input where parsing failed.
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the cause of the failure.
+    #[inline]
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl Display for ParseBitMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let cause = match self.kind {
+            ParseErrorKind::Empty => "missing digits",
+            ParseErrorKind::InvalidCharacter => "invalid character",
+            ParseErrorKind::OutOfRange => "bit index out of range",
+            ParseErrorKind::InvalidRange => "range start greater than range end",
+            ParseErrorKind::LengthMismatch => "wrong number of bits",
+        };
+        write!(f, "{cause} at position {}", self.position)
+    }
+}
+
+impl core::error::Error for ParseBitMapError {}
+
+/// Cause of a [`ParseBitMapError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// Digits or an index are missing, e.g. after a prefix or between commas.
+    Empty,
+    /// A character is not valid at its position.
+    InvalidCharacter,
+    /// An index or set bit lies beyond `BIT_COUNT`.
+    OutOfRange,
+    /// An index range starts after its end.
+    InvalidRange,
+    /// The `Debug` layout holds more or fewer than `BIT_COUNT` bits.
+    LengthMismatch,
+}
@@ -0,0 +1,212 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+use core::ops::Range;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Reverses the order of all bits, moving the bit at index `i` to index
+    /// `BIT_COUNT - 1 - i`.
+    ///
+    /// Works on whole buckets instead of single bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 1, 7]);
+    /// bm.reverse_bits();
+    /// assert_eq!(bm, BitMap::from_ones_iter([2, 8, 9]));
+    /// ```
+    pub fn reverse_bits(&mut self) {
+        // reversing the buckets reverses all `BUCKET_COUNT * 8` bits, which
+        // leaves the reversed bitmap shifted up by the unused trailing bits
+        self.0.reverse();
+        for byte in &mut self.0 {
+            *byte = byte.reverse_bits();
+        }
+        let padding = BUCKET_COUNT * 8 - BIT_COUNT;
+        if padding != 0 {
+            for i in 0..BUCKET_COUNT {
+                let high = self.0.get(i + 1).copied().unwrap_or(0);
+                self.0[i] = self.0[i] >> padding | high << (8 - padding);
+            }
+        }
+        self.clean_unused_bits();
+    }
+
+    /// Swaps the bits at indices `a` and `b`.
+    ///
+    /// # Panics
+    /// Panics if `a >= BIT_COUNT` or `b >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1]);
+    /// bm.swap(1, 9);
+    /// assert_eq!(bm, BitMap::from_ones_iter([9]));
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let (a_set, b_set) = (self.is_set(a), self.is_set(b));
+        if a_set != b_set {
+            self.toggle(a);
+            self.toggle(b);
+        }
+    }
+
+    /// Swaps the bits in range `a` with the bits in the equally long range
+    /// `b`.
+    ///
+    /// Moves up to 64 bits at a time instead of single bits.
+    ///
+    /// # Panics
+    /// Panics if the ranges differ in length, overlap or reach beyond
+    /// `BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 1, 5]);
+    /// bm.swap_ranges(0..3, 4..7);
+    /// assert_eq!(bm, BitMap::from_ones_iter([1, 4, 5]));
+    /// ```
+    pub fn swap_ranges(&mut self, a: Range<usize>, b: Range<usize>) {
+        assert!(a.end <= BIT_COUNT, "Range end {} out of bounds", a.end);
+        assert!(b.end <= BIT_COUNT, "Range end {} out of bounds", b.end);
+        assert_eq!(a.len(), b.len(), "Ranges must have the same length.");
+        assert!(
+            a.is_empty() || a.end <= b.start || b.end <= a.start,
+            "Ranges must not overlap."
+        );
+
+        let mut offset = 0;
+        while offset < a.len() {
+            let len = (a.len() - offset).min(64);
+            let a_bits = self.read_bits(a.start + offset, len);
+            let b_bits = self.read_bits(b.start + offset, len);
+            self.write_bits(a.start + offset, len, b_bits);
+            self.write_bits(b.start + offset, len, a_bits);
+            offset += len;
+        }
+    }
+
+    /// Moves the bit at every index `i` to index `table[i]`.
+    ///
+    /// Validates `table` on every call. Use a [`Permutation`] to apply the
+    /// same table repeatedly without revalidating it.
+    ///
+    /// # Panics
+    /// Panics if `table` is not a permutation of `0..BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<4, { bucket_count(4) }>::from_ones_iter([0, 1]);
+    /// bm.permute(&[2, 0, 3, 1]);
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 2]));
+    /// ```
+    pub fn permute(&mut self, table: &[usize; BIT_COUNT]) {
+        assert!(
+            is_permutation::<BIT_COUNT, BUCKET_COUNT>(table),
+            "Table must be a permutation of 0..BIT_COUNT."
+        );
+        self.permute_unchecked(table);
+    }
+
+    /// Moves the bits according to a precomputed [`Permutation`].
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, Permutation, bucket_count};
+    ///
+    /// let perm = Permutation::<4, { bucket_count(4) }>::new([2, 0, 3, 1]).unwrap();
+    /// let mut bm = BitMap::<4, { bucket_count(4) }>::from_ones_iter([0, 1]);
+    /// bm.apply_permutation(&perm);
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 2]));
+    /// bm.apply_permutation(&perm.inverse());
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 1]));
+    /// ```
+    #[inline]
+    pub fn apply_permutation(&mut self, permutation: &Permutation<BIT_COUNT, BUCKET_COUNT>) {
+        self.permute_unchecked(&permutation.table);
+    }
+
+    fn permute_unchecked(&mut self, table: &[usize; BIT_COUNT]) {
+        let mut result = Self::new();
+        for idx in self.iter_ones() {
+            result.set(table[idx]);
+        }
+        *self = result;
+    }
+}
+
+fn is_permutation<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    table: &[usize; BIT_COUNT],
+) -> bool {
+    let mut seen = BitMap::<BIT_COUNT, BUCKET_COUNT>::new();
+    table
+        .iter()
+        .all(|&target| target < BIT_COUNT && !seen.toggle(target))
+}
+
+/// A validated permutation of the indices `0..BIT_COUNT`.
+///
+/// Maps every index `i` to `table[i]`. Validated once on construction, so it
+/// can be applied with [`BitMap::apply_permutation()`] repeatedly without
+/// checking the table again.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, Permutation, bucket_count};
+///
+/// // interleaves the two halves of an 8 bit block
+/// let interleave = Permutation::<8, { bucket_count(8) }>::new([0, 2, 4, 6, 1, 3, 5, 7]).unwrap();
+/// let mut block = BitMap::<8, { bucket_count(8) }>::from_ones_iter([0, 1, 2, 3]);
+/// block.apply_permutation(&interleave);
+/// assert_eq!(block, BitMap::from_ones_iter([0, 2, 4, 6]));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Permutation<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    table: [usize; BIT_COUNT],
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Permutation<BIT_COUNT, BUCKET_COUNT> {
+    /// Creates a permutation mapping every index `i` to `table[i]`.
+    ///
+    /// Returns `None` if `table` is not a permutation of `0..BIT_COUNT`, i.e.
+    /// if it contains an index `>= BIT_COUNT` or an index twice.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    pub fn new(table: [usize; BIT_COUNT]) -> Option<Self> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        is_permutation::<BIT_COUNT, BUCKET_COUNT>(&table).then_some(Self { table })
+    }
+
+    /// Creates the permutation that leaves every index in place.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    pub fn identity() -> Self {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        Self {
+            table: core::array::from_fn(|idx| idx),
+        }
+    }
+
+    /// Returns the permutation that undoes this one.
+    pub fn inverse(&self) -> Self {
+        let mut table = [0; BIT_COUNT];
+        for (idx, &target) in self.table.iter().enumerate() {
+            table[target] = idx;
+        }
+        Self { table }
+    }
+
+    /// Returns the target index of every index.
+    #[inline]
+    pub fn as_table(&self) -> &[usize; BIT_COUNT] {
+        &self.table
+    }
+}
@@ -0,0 +1,118 @@
+use crate::bitmap::BitMap;
+use core::ops::Range;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Finds the first run of `len` unset bits starting at a multiple of
+    /// `align`, sets it and returns its range.
+    ///
+    /// Set bits mark allocated slots. Use an `align` of 1 for unaligned
+    /// allocations. Returns `None` if no such run exists.
+    ///
+    /// # Panics
+    /// Panics if `len == 0` or `align == 0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut pool = BitMap::<16, { bucket_count(16) }>::from_ones_iter([1]);
+    /// assert_eq!(pool.alloc_range(3, 1), Some(2..5));
+    /// assert_eq!(pool.alloc_range(3, 4), Some(8..11));
+    /// assert_eq!(pool.alloc_range(6, 1), None);
+    /// ```
+    pub fn alloc_range(&mut self, len: usize, align: usize) -> Option<Range<usize>> {
+        assert_alloc_params(len, align);
+        let range = self
+            .iter_zero_runs()
+            .find_map(|run| aligned_fit(run, len, align))?;
+        self.set_range(range.clone());
+        Some(range)
+    }
+
+    /// Finds the smallest run of unset bits that can hold `len` bits starting
+    /// at a multiple of `align`, sets those bits and returns their range.
+    ///
+    /// Set bits mark allocated slots. Use an `align` of 1 for unaligned
+    /// allocations. Among equally sized runs the first one is used. Returns
+    /// `None` if no such run exists.
+    ///
+    /// # Panics
+    /// Panics if `len == 0` or `align == 0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut pool = BitMap::<16, { bucket_count(16) }>::new();
+    /// pool.set_range(6..13);
+    /// assert_eq!(pool.alloc_range_best_fit(3, 1), Some(13..16));
+    /// assert_eq!(pool.alloc_range_best_fit(3, 1), Some(0..3));
+    /// ```
+    pub fn alloc_range_best_fit(&mut self, len: usize, align: usize) -> Option<Range<usize>> {
+        assert_alloc_params(len, align);
+        let (_, range) = self
+            .iter_zero_runs()
+            .filter_map(|run| Some((run.len(), aligned_fit(run, len, align)?)))
+            .reduce(|best, candidate| {
+                if candidate.0 < best.0 {
+                    candidate
+                } else {
+                    best
+                }
+            })?;
+        self.set_range(range.clone());
+        Some(range)
+    }
+
+    /// Unsets all bits in the given range if all of them are set.
+    ///
+    /// Returns `false` and leaves the bitmap unchanged if any bit in the range
+    /// is unset, which catches double frees and frees of ranges that were
+    /// never allocated.
+    ///
+    /// # Panics
+    /// Panics if `range.start >= BIT_COUNT` or `range.end > BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut pool = BitMap::<16, { bucket_count(16) }>::new();
+    /// let block = pool.alloc_range(4, 1).unwrap();
+    /// assert!(pool.free_range(block.clone()));
+    /// assert!(!pool.free_range(block));
+    /// ```
+    #[must_use]
+    pub fn free_range(&mut self, range: Range<usize>) -> bool {
+        assert!(
+            range.start < BIT_COUNT,
+            "Range start {} out of bounds",
+            range.start
+        );
+        assert!(
+            range.end <= BIT_COUNT,
+            "Range end {} out of bounds",
+            range.end
+        );
+
+        let fully_set = self
+            .first_unset_bit_from(range.start)
+            .is_none_or(|idx| idx >= range.end);
+        if fully_set {
+            self.unset_range(range);
+        }
+        fully_set
+    }
+}
+
+fn assert_alloc_params(len: usize, align: usize) {
+    assert_ne!(len, 0, "Allocation length must be greater than zero.");
+    assert_ne!(align, 0, "Alignment must be greater than zero.");
+}
+
+/// Returns the first `len` bits of `run` that start at a multiple of `align`.
+fn aligned_fit(run: Range<usize>, len: usize, align: usize) -> Option<Range<usize>> {
+    let start = run.start.next_multiple_of(align);
+    let end = start.checked_add(len)?;
+    (end <= run.end).then_some(start..end)
+}
@@ -0,0 +1,156 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Converts the bitmap into one of `NEW_BIT_COUNT` bits.
+    ///
+    /// Growing appends unset bits, shrinking drops all bits at and above
+    /// `NEW_BIT_COUNT`. Copies whole buckets.
+    ///
+    /// # Panics
+    /// Panics if `NEW_BIT_COUNT == 0` or
+    /// `NEW_BUCKET_COUNT != bucket_count(NEW_BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([1, 11]);
+    /// let wide = bm.resize::<64, { bucket_count(64) }>();
+    /// assert_eq!(wide, BitMap::from_ones_iter([1, 11]));
+    /// let narrow = bm.resize::<8, { bucket_count(8) }>();
+    /// assert_eq!(narrow, BitMap::from_ones_iter([1]));
+    /// ```
+    pub fn resize<const NEW_BIT_COUNT: usize, const NEW_BUCKET_COUNT: usize>(
+        &self,
+    ) -> BitMap<NEW_BIT_COUNT, NEW_BUCKET_COUNT> {
+        runtime_assert_const_params(NEW_BIT_COUNT, NEW_BUCKET_COUNT);
+        let mut result = BitMap([0u8; NEW_BUCKET_COUNT]);
+        let len = BUCKET_COUNT.min(NEW_BUCKET_COUNT);
+        result.0[..len].copy_from_slice(&self.0[..len]);
+        result.clean_unused_bits();
+        result
+    }
+
+    /// Converts the bitmap into one of `NEW_BIT_COUNT` bits, or returns `None`
+    /// if any bit at or above `NEW_BIT_COUNT` is set and would be lost.
+    ///
+    /// # Panics
+    /// Panics if `NEW_BIT_COUNT == 0` or
+    /// `NEW_BUCKET_COUNT != bucket_count(NEW_BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<64, { bucket_count(64) }>::from_ones_iter([1, 11]);
+    /// assert_eq!(
+    ///     bm.try_narrow::<12, { bucket_count(12) }>(),
+    ///     Some(BitMap::from_ones_iter([1, 11]))
+    /// );
+    /// assert_eq!(bm.try_narrow::<11, { bucket_count(11) }>(), None);
+    /// ```
+    pub fn try_narrow<const NEW_BIT_COUNT: usize, const NEW_BUCKET_COUNT: usize>(
+        &self,
+    ) -> Option<BitMap<NEW_BIT_COUNT, NEW_BUCKET_COUNT>> {
+        let result = self.resize();
+        self.first_set_bit_from(NEW_BIT_COUNT)
+            .is_none()
+            .then_some(result)
+    }
+
+    /// Joins two bitmaps into one whose low bits are `low` and whose bits
+    /// from `LOW_BIT_COUNT` upwards are `high`.
+    ///
+    /// Works on whole buckets, shifting `high` into place if `LOW_BIT_COUNT`
+    /// is not a multiple of 8.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT != LOW_BIT_COUNT + HIGH_BIT_COUNT`, if
+    /// `BIT_COUNT == 0` or if `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let low = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 11]);
+    /// let high = BitMap::<6, { bucket_count(6) }>::from_ones_iter([1, 5]);
+    /// let joined = BitMap::<18, { bucket_count(18) }>::concat(&low, &high);
+    /// assert_eq!(joined, BitMap::from_ones_iter([0, 11, 13, 17]));
+    /// ```
+    pub fn concat<
+        const LOW_BIT_COUNT: usize,
+        const LOW_BUCKET_COUNT: usize,
+        const HIGH_BIT_COUNT: usize,
+        const HIGH_BUCKET_COUNT: usize,
+    >(
+        low: &BitMap<LOW_BIT_COUNT, LOW_BUCKET_COUNT>,
+        high: &BitMap<HIGH_BIT_COUNT, HIGH_BUCKET_COUNT>,
+    ) -> Self {
+        assert_eq!(
+            BIT_COUNT,
+            LOW_BIT_COUNT + HIGH_BIT_COUNT,
+            "BIT_COUNT must be the sum of both input BIT_COUNTs."
+        );
+        let mut result = low.resize::<BIT_COUNT, BUCKET_COUNT>();
+        let (start_byte, start_bit) = (LOW_BIT_COUNT / 8, LOW_BIT_COUNT % 8);
+        for (i, &byte) in high.0.iter().enumerate() {
+            result.0[start_byte + i] |= byte << start_bit;
+            if start_bit != 0 && start_byte + i + 1 < BUCKET_COUNT {
+                result.0[start_byte + i + 1] |= byte >> (8 - start_bit);
+            }
+        }
+        result
+    }
+
+    /// Splits the bitmap into its bits below `LOW_BIT_COUNT` and the bits
+    /// from `LOW_BIT_COUNT` upwards, shifted down to index 0.
+    ///
+    /// Inverse of [`concat`]. Works on whole buckets, shifting the high part
+    /// into place if `LOW_BIT_COUNT` is not a multiple of 8.
+    ///
+    /// # Panics
+    /// Panics if `LOW_BIT_COUNT + HIGH_BIT_COUNT != BIT_COUNT`, if either
+    /// count is zero or if a bucket count does not match its bit count.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<18, { bucket_count(18) }>::from_ones_iter([0, 11, 13, 17]);
+    /// let (low, high): (BitMap<12, { bucket_count(12) }>, BitMap<6, { bucket_count(6) }>) =
+    ///     bm.split_at();
+    /// assert_eq!(low, BitMap::from_ones_iter([0, 11]));
+    /// assert_eq!(high, BitMap::from_ones_iter([1, 5]));
+    /// ```
+    ///
+    /// [`concat`]: BitMap::concat
+    pub fn split_at<
+        const LOW_BIT_COUNT: usize,
+        const LOW_BUCKET_COUNT: usize,
+        const HIGH_BIT_COUNT: usize,
+        const HIGH_BUCKET_COUNT: usize,
+    >(
+        &self,
+    ) -> (
+        BitMap<LOW_BIT_COUNT, LOW_BUCKET_COUNT>,
+        BitMap<HIGH_BIT_COUNT, HIGH_BUCKET_COUNT>,
+    ) {
+        assert_eq!(
+            LOW_BIT_COUNT + HIGH_BIT_COUNT,
+            BIT_COUNT,
+            "Both output BIT_COUNTs must sum up to BIT_COUNT."
+        );
+        runtime_assert_const_params(HIGH_BIT_COUNT, HIGH_BUCKET_COUNT);
+        let low = self.resize();
+        let mut high = BitMap([0u8; HIGH_BUCKET_COUNT]);
+        let (start_byte, start_bit) = (LOW_BIT_COUNT / 8, LOW_BIT_COUNT % 8);
+        for (i, byte) in high.0.iter_mut().enumerate() {
+            *byte = self.0[start_byte + i] >> start_bit;
+            if start_bit != 0 && start_byte + i + 1 < BUCKET_COUNT {
+                *byte |= self.0[start_byte + i + 1] << (8 - start_bit);
+            }
+        }
+        high.clean_unused_bits();
+        (low, high)
+    }
+}
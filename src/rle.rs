@@ -0,0 +1,215 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+use crate::codec::{CodecError, VarintReader, varint_len, write_varint};
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns the maximum number of bytes [`encode_rle`] writes for any
+    /// bitmap of this size.
+    ///
+    /// Usable in const contexts, e.g. to size a buffer on the stack.
+    ///
+    /// [`encode_rle`]: BitMap::encode_rle
+    #[inline]
+    pub const fn max_rle_len() -> usize {
+        // every varint byte covers at least one bit, except for the possibly
+        // empty leading run of unset bits
+        BIT_COUNT + 1
+    }
+
+    /// Encodes the bitmap as run lengths into `out` and returns the number of
+    /// bytes written.
+    ///
+    /// The encoding is a sequence of unsigned LEB128 varints (7 bits per
+    /// byte, least significant group first, high bit set on all but the last
+    /// byte) giving the lengths of alternating runs of unset and set bits,
+    /// starting at bit 0 with a run of unset bits. Only this first run may be
+    /// empty, all others are at least 1 bit long, and the runs add up to
+    /// exactly `BIT_COUNT`. `BIT_COUNT` itself is not stored.
+    ///
+    /// An empty bitmap encodes as the single varint `BIT_COUNT`, a bitmap with
+    /// only bit 0 set as `0, 1, BIT_COUNT - 1`.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::BufferTooSmall`] if `out` is too small. A buffer
+    /// of [`max_rle_len`] bytes always suffices.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<1000, { bucket_count(1000) }>;
+    /// let mut bm = Bm::new();
+    /// bm.set_range(10..300);
+    /// let mut buf = [0u8; Bm::max_rle_len()];
+    /// let len = bm.encode_rle(&mut buf).unwrap();
+    /// assert_eq!(&buf[..len], &[10, 0xa2, 0x02, 0xbc, 0x05]);
+    /// assert_eq!(Bm::decode_rle(&buf[..len]), Ok(bm));
+    /// ```
+    ///
+    /// [`max_rle_len`]: BitMap::max_rle_len
+    pub fn encode_rle(&self, out: &mut [u8]) -> Result<usize, CodecError> {
+        let mut written = 0;
+        self.for_each_run_len(|len| write_varint(out, &mut written, len))?;
+        Ok(written)
+    }
+
+    /// Returns the number of bytes [`encode_rle`] writes for this bitmap.
+    ///
+    /// [`encode_rle`]: BitMap::encode_rle
+    pub(crate) fn rle_len(&self) -> usize {
+        let mut len = 0;
+        let _ = self.for_each_run_len(|run_len| {
+            len += varint_len(run_len);
+            Ok(())
+        });
+        len
+    }
+
+    /// Calls `f` with the lengths of the alternating runs, starting with the
+    /// possibly empty run of unset bits at bit 0.
+    fn for_each_run_len<F>(&self, mut f: F) -> Result<(), CodecError>
+    where
+        F: FnMut(usize) -> Result<(), CodecError>,
+    {
+        let mut pos = 0;
+        for run in self.iter_runs() {
+            f(run.start - pos)?;
+            f(run.len())?;
+            pos = run.end;
+        }
+        if pos < BIT_COUNT {
+            f(BIT_COUNT - pos)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes a bitmap from the run length encoding written by
+    /// [`encode_rle`].
+    ///
+    /// Use an [`RleDecoder`] to decode input arriving in pieces.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if `bytes` is not exactly one valid encoding
+    /// of a bitmap of `BIT_COUNT` bits.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, CodecError, bucket_count};
+    ///
+    /// type Bm = BitMap<10, { bucket_count(10) }>;
+    /// assert_eq!(Bm::decode_rle(&[2, 3, 5]), Ok(Bm::from_ones_iter([2, 3, 4])));
+    /// assert_eq!(Bm::decode_rle(&[2, 3]), Err(CodecError::UnexpectedEnd));
+    /// assert_eq!(Bm::decode_rle(&[2, 9]), Err(CodecError::InvalidRun));
+    /// ```
+    ///
+    /// [`encode_rle`]: BitMap::encode_rle
+    pub fn decode_rle(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut decoder = RleDecoder::new();
+        decoder.feed(bytes)?;
+        decoder.finish()
+    }
+}
+
+/// Streaming decoder for the run length encoding written by
+/// [`BitMap::encode_rle()`].
+///
+/// Accepts the input in arbitrarily split pieces, e.g. as packets arrive,
+/// and writes the runs straight into the bitmap without buffering the input.
+/// Once an error has been returned, the decoder should be discarded.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, RleDecoder, bucket_count};
+///
+/// type Bm = BitMap<1000, { bucket_count(1000) }>;
+/// let mut decoder = RleDecoder::<1000, { bucket_count(1000) }>::new();
+/// decoder.feed(&[10, 0xa2]).unwrap();
+/// assert!(!decoder.is_complete());
+/// decoder.feed(&[0x02, 0xbc, 0x05]).unwrap();
+/// assert!(decoder.is_complete());
+/// let mut expected = Bm::new();
+/// expected.set_range(10..300);
+/// assert_eq!(decoder.finish(), Ok(expected));
+/// ```
+#[derive(Clone, Copy)]
+pub struct RleDecoder<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: BitMap<BIT_COUNT, BUCKET_COUNT>,
+    pos: usize,
+    next_is_set: bool,
+    varint: VarintReader,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> RleDecoder<BIT_COUNT, BUCKET_COUNT> {
+    /// Creates a decoder expecting the first run at bit 0.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    pub fn new() -> Self {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        Self {
+            bitmap: BitMap::new(),
+            pos: 0,
+            next_is_set: false,
+            varint: VarintReader::default(),
+        }
+    }
+
+    /// Decodes the next piece of input.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::InvalidVarint`] or [`CodecError::InvalidRun`] if
+    /// the input is malformed and [`CodecError::TrailingBytes`] if input
+    /// follows after the runs have reached `BIT_COUNT`.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<(), CodecError> {
+        for &byte in bytes {
+            if self.pos == BIT_COUNT {
+                return Err(CodecError::TrailingBytes);
+            }
+            if let Some(len) = self.varint.push(byte)? {
+                self.push_run(len)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the runs decoded so far cover all `BIT_COUNT` bits.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.pos == BIT_COUNT
+    }
+
+    /// Returns the decoded bitmap.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::UnexpectedEnd`] if the runs do not cover all
+    /// `BIT_COUNT` bits yet.
+    pub fn finish(self) -> Result<BitMap<BIT_COUNT, BUCKET_COUNT>, CodecError> {
+        if !self.is_complete() {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        Ok(self.bitmap)
+    }
+
+    fn push_run(&mut self, len: usize) -> Result<(), CodecError> {
+        let leading_zero_run = self.pos == 0 && !self.next_is_set;
+        if (len == 0 && !leading_zero_run) || len > BIT_COUNT - self.pos {
+            return Err(CodecError::InvalidRun);
+        }
+        if self.next_is_set {
+            self.bitmap.set_range(self.pos..self.pos + len);
+        }
+        self.pos += len;
+        self.next_is_set = !self.next_is_set;
+        Ok(())
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Default
+    for RleDecoder<BIT_COUNT, BUCKET_COUNT>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,654 @@
+use crate::bitmap::{BitMap, IterOnes};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::iter::FusedIterator;
+use core::slice;
+
+/// Dense container covering all 2^16 values of a chunk.
+type Dense = BitMap<65536, 8192>;
+
+/// Maximum number of values in an array container, above which a bitmap
+/// container takes less memory.
+const ARRAY_MAX: usize = 4096;
+
+/// Maximum number of runs in a run container, above which a bitmap container
+/// takes less memory.
+const RUN_MAX: usize = 2048;
+
+/// A compressed set of `u32` values, split into chunks of 2^16 values.
+///
+/// Each non-empty chunk is stored in whichever container fits its contents:
+///
+/// - an array of sorted values for up to 4096 values
+/// - a `BitMap<65536, 8192>` for more values
+/// - a list of runs of consecutive values, chosen by bulk operations like
+///   [`insert_bitmap`] and by [`optimize`] when it is the smallest
+///
+/// Single insertions and removals switch between array and bitmap containers
+/// as the chunk grows and shrinks. Requires the `alloc` feature.
+///
+/// # Examples
+/// ```
+/// use light_bitmap::{BitMap, RoaringBitMap, bucket_count};
+///
+/// let mut set = RoaringBitMap::new();
+/// set.insert(7);
+/// set.insert(3_000_000_000);
+/// let block = BitMap::<100, { bucket_count(100) }>::with_all_set();
+/// set.insert_bitmap(65_500, &block);
+/// assert_eq!(set.len(), 102);
+/// assert!(set.contains(65_599));
+///
+/// let window: BitMap<64, { bucket_count(64) }> = set.extract_bitmap(65_472);
+/// assert_eq!(window.iter_ones().next(), Some(28));
+/// ```
+///
+/// [`insert_bitmap`]: RoaringBitMap::insert_bitmap
+/// [`optimize`]: RoaringBitMap::optimize
+#[derive(Clone, Default)]
+pub struct RoaringBitMap {
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Clone)]
+struct Chunk {
+    key: u16,
+    container: Container,
+}
+
+#[derive(Clone)]
+enum Container {
+    /// Sorted values.
+    Array(Vec<u16>),
+    /// All values as bits, together with the number of set bits.
+    Bitmap(Box<Dense>, u32),
+    /// Sorted runs of consecutive values as inclusive `(first, last)` pairs,
+    /// with gaps between them.
+    Run(Vec<(u16, u16)>),
+}
+
+impl RoaringBitMap {
+    /// Creates an empty set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { chunks: Vec::new() }
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns `true` if the value was not present before.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::RoaringBitMap;
+    ///
+    /// let mut set = RoaringBitMap::new();
+    /// assert!(set.insert(70_000));
+    /// assert!(!set.insert(70_000));
+    /// ```
+    pub fn insert(&mut self, value: u32) -> bool {
+        let (key, low) = split(value);
+        let idx = self.find(key).unwrap_or_else(|idx| {
+            let container = Container::Array(Vec::new());
+            self.chunks.insert(idx, Chunk { key, container });
+            idx
+        });
+        self.chunks[idx].container.insert(low)
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// Returns `true` if the value was present before.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::RoaringBitMap;
+    ///
+    /// let mut set: RoaringBitMap = [1, 70_000].into_iter().collect();
+    /// assert!(set.remove(70_000));
+    /// assert!(!set.remove(70_000));
+    /// ```
+    pub fn remove(&mut self, value: u32) -> bool {
+        let (key, low) = split(value);
+        let Ok(idx) = self.find(key) else {
+            return false;
+        };
+        let removed = self.chunks[idx].container.remove(low);
+        if self.chunks[idx].container.is_empty() {
+            self.chunks.remove(idx);
+        }
+        removed
+    }
+
+    /// Returns `true` if the set contains the value.
+    pub fn contains(&self, value: u32) -> bool {
+        let (key, low) = split(value);
+        self.find(key)
+            .is_ok_and(|idx| self.chunks[idx].container.contains(low))
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.container.len() as u64)
+            .sum()
+    }
+
+    /// Returns `true` if the set contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Removes all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    /// Returns an iterator over all values in ascending order.
+    #[inline]
+    pub fn iter(&self) -> RoaringIter<'_> {
+        RoaringIter {
+            chunks: self.chunks.iter(),
+            high: 0,
+            values: ContainerIter::Array([].iter()),
+        }
+    }
+
+    /// Adds the values `offset + i` for every set bit `i` of `bitmap`.
+    ///
+    /// Works chunk by chunk, copying the covered part of `bitmap` into a
+    /// dense container with [`BitMap::copy_range_from()`] and storing the
+    /// merged chunk in its smallest container.
+    ///
+    /// # Panics
+    /// Panics if `offset + BIT_COUNT` exceeds 2^32.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, RoaringBitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 9]);
+    /// let mut set = RoaringBitMap::new();
+    /// set.insert_bitmap(65_530, &bm);
+    /// assert!(set.iter().eq([65_530, 65_539]));
+    /// ```
+    pub fn insert_bitmap<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+        &mut self,
+        offset: u32,
+        bitmap: &BitMap<BIT_COUNT, BUCKET_COUNT>,
+    ) {
+        assert_fits::<BIT_COUNT>(offset);
+        for_each_chunk_part::<BIT_COUNT>(offset, |key, low, bits| {
+            let mut dense = Dense::new();
+            dense.copy_range_from(bitmap, bits, low);
+            if dense.first_set_bit().is_none() {
+                return;
+            }
+            match self.find(key) {
+                Ok(idx) => {
+                    dense.in_place_bit_or(&self.chunks[idx].container.to_dense());
+                    if let Some(container) = Container::from_dense(&dense) {
+                        self.chunks[idx].container = container;
+                    }
+                }
+                Err(idx) => {
+                    if let Some(container) = Container::from_dense(&dense) {
+                        self.chunks.insert(idx, Chunk { key, container });
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns the values `offset..offset + BIT_COUNT` as a bitmap, value
+    /// `offset + i` becoming bit `i`.
+    ///
+    /// # Panics
+    /// Panics if `offset + BIT_COUNT` exceeds 2^32, if `BIT_COUNT == 0` or if
+    /// `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, RoaringBitMap, bucket_count};
+    ///
+    /// let set: RoaringBitMap = [5, 65_536, 65_540].into_iter().collect();
+    /// let bm: BitMap<8, { bucket_count(8) }> = set.extract_bitmap(65_534);
+    /// assert_eq!(bm, BitMap::from_ones_iter([2, 6]));
+    /// ```
+    pub fn extract_bitmap<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+        &self,
+        offset: u32,
+    ) -> BitMap<BIT_COUNT, BUCKET_COUNT> {
+        assert_fits::<BIT_COUNT>(offset);
+        let mut bitmap = BitMap::new();
+        for_each_chunk_part::<BIT_COUNT>(offset, |key, low, bits| {
+            if let Ok(idx) = self.find(key) {
+                let values = low..low + bits.len();
+                self.chunks[idx]
+                    .container
+                    .copy_range_into(values, &mut bitmap, bits.start);
+            }
+        });
+        bitmap
+    }
+
+    /// Returns the values of the chunk `key`, i.e. of
+    /// `key << 16..(key + 1) << 16`, as a bitmap with value
+    /// `(key << 16) + i` as bit `i`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::RoaringBitMap;
+    ///
+    /// let set: RoaringBitMap = [3, 65_536 + 9].into_iter().collect();
+    /// assert!(set.chunk(1).iter_ones().eq([9]));
+    /// assert_eq!(set.chunk(2).popcount(), 0);
+    /// ```
+    pub fn chunk(&self, key: u16) -> BitMap<65536, 8192> {
+        match self.find(key) {
+            Ok(idx) => self.chunks[idx].container.to_dense(),
+            Err(_) => Dense::new(),
+        }
+    }
+
+    /// Converts every chunk into its smallest container, turning chunks of
+    /// few long runs into run containers.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::RoaringBitMap;
+    ///
+    /// let mut set: RoaringBitMap = (0..50_000).collect();
+    /// set.optimize();
+    /// assert_eq!(set.len(), 50_000);
+    /// ```
+    pub fn optimize(&mut self) {
+        for chunk in &mut self.chunks {
+            if let Some(container) = Container::from_dense(&chunk.container.to_dense()) {
+                chunk.container = container;
+            }
+        }
+    }
+
+    fn find(&self, key: u16) -> Result<usize, usize> {
+        self.chunks.binary_search_by_key(&key, |chunk| chunk.key)
+    }
+}
+
+/// The kind of container storing a chunk, for checking container transitions
+/// in tests.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ContainerKind {
+    Array,
+    Bitmap,
+    Run,
+}
+
+#[cfg(test)]
+impl RoaringBitMap {
+    /// Returns the kind of container storing the chunk `key`, or `None` if
+    /// the chunk is empty.
+    pub(crate) fn container_kind(&self, key: u16) -> Option<ContainerKind> {
+        let idx = self.find(key).ok()?;
+        Some(match self.chunks[idx].container {
+            Container::Array(_) => ContainerKind::Array,
+            Container::Bitmap(..) => ContainerKind::Bitmap,
+            Container::Run(_) => ContainerKind::Run,
+        })
+    }
+}
+
+impl Container {
+    /// Returns the smallest container holding the set bits of `dense`, or
+    /// `None` if it has none.
+    fn from_dense(dense: &Dense) -> Option<Self> {
+        let len = dense.popcount();
+        if len == 0 {
+            return None;
+        }
+        let runs = dense.iter_runs().count();
+        // 2 bytes per array value, 4 bytes per run and 8 KiB for the bitmap
+        Some(if 4 * runs < (2 * len).min(8192) {
+            let runs = dense.iter_runs();
+            Container::Run(
+                runs.map(|run| (run.start as u16, (run.end - 1) as u16))
+                    .collect(),
+            )
+        } else if len <= ARRAY_MAX {
+            Container::Array(dense.iter_ones().map(|value| value as u16).collect())
+        } else {
+            Container::Bitmap(Box::new(*dense), len as u32)
+        })
+    }
+
+    fn to_dense(&self) -> Dense {
+        match self {
+            Container::Array(values) => Dense::from_ones_iter(values.iter().map(|&v| v as usize)),
+            Container::Bitmap(bits, _) => **bits,
+            Container::Run(runs) => {
+                let mut dense = Dense::new();
+                for &(first, last) in runs {
+                    dense.set_range(first as usize..last as usize + 1);
+                }
+                dense
+            }
+        }
+    }
+
+    /// Copies the `values` of this container into `bitmap`, value
+    /// `values.start` becoming bit `dest`, without building a dense copy of
+    /// array and run containers.
+    fn copy_range_into<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+        &self,
+        values: core::ops::Range<usize>,
+        bitmap: &mut BitMap<BIT_COUNT, BUCKET_COUNT>,
+        dest: usize,
+    ) {
+        match self {
+            Container::Array(array) => {
+                let start = array.partition_point(|&value| (value as usize) < values.start);
+                for &value in array[start..]
+                    .iter()
+                    .take_while(|&&value| (value as usize) < values.end)
+                {
+                    bitmap.set(dest + value as usize - values.start);
+                }
+            }
+            Container::Bitmap(bits, _) => bitmap.copy_range_from(bits, values, dest),
+            Container::Run(runs) => {
+                let start = runs.partition_point(|&(_, last)| (last as usize) < values.start);
+                for &(first, last) in runs[start..]
+                    .iter()
+                    .take_while(|&&(first, _)| (first as usize) < values.end)
+                {
+                    let first = (first as usize).max(values.start);
+                    let end = (last as usize + 1).min(values.end);
+                    bitmap.set_range(dest + first - values.start..dest + end - values.start);
+                }
+            }
+        }
+    }
+
+    fn len(&self) -> u32 {
+        match self {
+            Container::Array(values) => values.len() as u32,
+            Container::Bitmap(_, len) => *len,
+            Container::Run(runs) => runs
+                .iter()
+                .map(|&(first, last)| (last - first) as u32 + 1)
+                .sum(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Container::Array(values) => values.is_empty(),
+            Container::Bitmap(_, len) => *len == 0,
+            Container::Run(runs) => runs.is_empty(),
+        }
+    }
+
+    fn contains(&self, value: u16) -> bool {
+        match self {
+            Container::Array(values) => values.binary_search(&value).is_ok(),
+            Container::Bitmap(bits, _) => bits.is_set(value as usize),
+            Container::Run(runs) => find_run(runs, value).is_ok(),
+        }
+    }
+
+    fn insert(&mut self, value: u16) -> bool {
+        let inserted = match self {
+            Container::Array(values) => match values.binary_search(&value) {
+                Ok(_) => false,
+                Err(idx) => {
+                    values.insert(idx, value);
+                    true
+                }
+            },
+            Container::Bitmap(bits, len) => {
+                let was_set = bits.test_and_set(value as usize);
+                *len += u32::from(!was_set);
+                !was_set
+            }
+            Container::Run(runs) => insert_into_runs(runs, value),
+        };
+        self.rebalance();
+        inserted
+    }
+
+    fn remove(&mut self, value: u16) -> bool {
+        let removed = match self {
+            Container::Array(values) => match values.binary_search(&value) {
+                Ok(idx) => {
+                    values.remove(idx);
+                    true
+                }
+                Err(_) => false,
+            },
+            Container::Bitmap(bits, len) => {
+                let was_set = bits.test_and_unset(value as usize);
+                *len -= u32::from(was_set);
+                was_set
+            }
+            Container::Run(runs) => remove_from_runs(runs, value),
+        };
+        self.rebalance();
+        removed
+    }
+
+    /// Switches to another container type once this one has grown or shrunk
+    /// past the size where the other one takes less memory.
+    fn rebalance(&mut self) {
+        match self {
+            Container::Array(values) if values.len() > ARRAY_MAX => {
+                let len = values.len() as u32;
+                *self = Container::Bitmap(Box::new(self.to_dense()), len);
+            }
+            Container::Bitmap(bits, len) if *len as usize <= ARRAY_MAX => {
+                *self = Container::Array(bits.iter_ones().map(|value| value as u16).collect());
+            }
+            Container::Run(runs) if runs.len() > RUN_MAX => {
+                if let Some(container) = Container::from_dense(&self.to_dense()) {
+                    *self = container;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(values) => ContainerIter::Array(values.iter()),
+            Container::Bitmap(bits, _) => ContainerIter::Bitmap(bits.iter_ones()),
+            Container::Run(runs) => ContainerIter::Run {
+                runs: runs.iter(),
+                next: 1,
+                last: 0,
+            },
+        }
+    }
+}
+
+/// Returns the index of the run containing `value`, or the index where a run
+/// starting at `value` would be inserted.
+fn find_run(runs: &[(u16, u16)], value: u16) -> Result<usize, usize> {
+    runs.binary_search_by(|&(first, last)| {
+        if last < value {
+            core::cmp::Ordering::Less
+        } else if first > value {
+            core::cmp::Ordering::Greater
+        } else {
+            core::cmp::Ordering::Equal
+        }
+    })
+}
+
+fn insert_into_runs(runs: &mut Vec<(u16, u16)>, value: u16) -> bool {
+    let Err(idx) = find_run(runs, value) else {
+        return false;
+    };
+    // the neighbouring runs end below and start above `value`, so neither
+    // `+ 1` overflows
+    let joins_prev = idx > 0 && runs[idx - 1].1 + 1 == value;
+    let joins_next = idx < runs.len() && runs[idx].0 == value + 1;
+    match (joins_prev, joins_next) {
+        (true, true) => {
+            runs[idx - 1].1 = runs[idx].1;
+            runs.remove(idx);
+        }
+        (true, false) => runs[idx - 1].1 = value,
+        (false, true) => runs[idx].0 = value,
+        (false, false) => runs.insert(idx, (value, value)),
+    }
+    true
+}
+
+fn remove_from_runs(runs: &mut Vec<(u16, u16)>, value: u16) -> bool {
+    let Ok(idx) = find_run(runs, value) else {
+        return false;
+    };
+    let (first, last) = runs[idx];
+    if first == last {
+        runs.remove(idx);
+    } else if value == first {
+        runs[idx].0 = value + 1;
+    } else if value == last {
+        runs[idx].1 = value - 1;
+    } else {
+        runs[idx].1 = value - 1;
+        runs.insert(idx + 1, (value + 1, last));
+    }
+    true
+}
+
+fn split(value: u32) -> (u16, u16) {
+    ((value >> 16) as u16, value as u16)
+}
+
+fn assert_fits<const BIT_COUNT: usize>(offset: u32) {
+    assert!(
+        offset as u64 + BIT_COUNT as u64 <= 1 << 32,
+        "Bitmap of {BIT_COUNT} bits does not fit at offset {offset}"
+    );
+}
+
+/// Calls `f` for every chunk overlapped by `offset..offset + BIT_COUNT` with
+/// the chunk key, the first covered value within the chunk and the covered
+/// bit range of the bitmap.
+fn for_each_chunk_part<const BIT_COUNT: usize>(
+    offset: u32,
+    mut f: impl FnMut(u16, usize, core::ops::Range<usize>),
+) {
+    let mut pos = 0;
+    while pos < BIT_COUNT {
+        let value = offset as u64 + pos as u64;
+        let (key, low) = ((value >> 16) as u16, (value & 0xffff) as usize);
+        let len = (65536 - low).min(BIT_COUNT - pos);
+        f(key, low, pos..pos + len);
+        pos += len;
+    }
+}
+
+impl PartialEq for RoaringBitMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for RoaringBitMap {}
+
+impl Debug for RoaringBitMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'set> IntoIterator for &'set RoaringBitMap {
+    type Item = u32;
+    type IntoIter = RoaringIter<'set>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Constructs a set from an iterator over values.
+impl FromIterator<u32> for RoaringBitMap {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Adds all values of the iterator to the set.
+impl Extend<u32> for RoaringBitMap {
+    fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/// Iterator over the values of a [`RoaringBitMap`] in ascending order.
+///
+/// Returned by [`RoaringBitMap::iter()`].
+#[derive(Clone)]
+pub struct RoaringIter<'set> {
+    chunks: slice::Iter<'set, Chunk>,
+    high: u32,
+    values: ContainerIter<'set>,
+}
+
+impl Iterator for RoaringIter<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(low) = self.values.next() {
+                return Some(self.high | low as u32);
+            }
+            let chunk = self.chunks.next()?;
+            self.high = (chunk.key as u32) << 16;
+            self.values = chunk.container.iter();
+        }
+    }
+}
+
+impl FusedIterator for RoaringIter<'_> {}
+
+#[derive(Clone)]
+enum ContainerIter<'set> {
+    Array(slice::Iter<'set, u16>),
+    Bitmap(IterOnes<'set, 65536, 8192>),
+    /// Yields `next..=last` of the current run before moving to the next.
+    Run {
+        runs: slice::Iter<'set, (u16, u16)>,
+        next: u32,
+        last: u32,
+    },
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ContainerIter::Array(values) => values.next().copied(),
+            ContainerIter::Bitmap(ones) => ones.next().map(|value| value as u16),
+            ContainerIter::Run { runs, next, last } => {
+                if next > last {
+                    let &(first, run_last) = runs.next()?;
+                    (*next, *last) = (first as u32, run_last as u32);
+                }
+                *next += 1;
+                Some((*next - 1) as u16)
+            }
+        }
+    }
+}
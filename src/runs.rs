@@ -0,0 +1,189 @@
+use crate::bitmap::BitMap;
+use core::iter::{FusedIterator, Iterator};
+use core::ops::Range;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns an iterator over the maximal runs of consecutive set bits, in
+    /// ascending order.
+    ///
+    /// Whole unset and set buckets are skipped instead of testing their bits
+    /// one by one.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// let mut runs = bm.iter_runs();
+    /// assert_eq!(runs.next(), Some(0..2));
+    /// assert_eq!(runs.next(), Some(5..9));
+    /// assert_eq!(runs.next(), Some(11..12));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter_runs(&self) -> IterRuns<'_, BIT_COUNT, BUCKET_COUNT> {
+        IterRuns {
+            bitmap: self,
+            pos: 0,
+        }
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive unset bits, in
+    /// ascending order.
+    ///
+    /// Whole unset and set buckets are skipped instead of testing their bits
+    /// one by one.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// let mut zero_runs = bm.iter_zero_runs();
+    /// assert_eq!(zero_runs.next(), Some(2..5));
+    /// assert_eq!(zero_runs.next(), Some(9..11));
+    /// assert_eq!(zero_runs.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter_zero_runs(&self) -> IterZeroRuns<'_, BIT_COUNT, BUCKET_COUNT> {
+        IterZeroRuns {
+            bitmap: self,
+            pos: 0,
+        }
+    }
+
+    /// Returns the longest run of consecutive set bits or `None` if all bits
+    /// are unset.
+    ///
+    /// If several runs share the maximum length, the first one is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// assert_eq!(bm.longest_run(), Some(5..9));
+    /// assert_eq!(BitMap::<12, { bucket_count(12) }>::new().longest_run(), None);
+    /// ```
+    pub fn longest_run(&self) -> Option<Range<usize>> {
+        longest(self.iter_runs())
+    }
+
+    /// Returns the longest run of consecutive unset bits or `None` if all bits
+    /// are set.
+    ///
+    /// If several runs share the maximum length, the first one is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// assert_eq!(bm.longest_zero_run(), Some(2..5));
+    /// ```
+    pub fn longest_zero_run(&self) -> Option<Range<usize>> {
+        longest(self.iter_zero_runs())
+    }
+
+    /// Returns the first maximal run of set bits that is at least `len` bits
+    /// long or `None` if there is no such run.
+    ///
+    /// The returned range covers the whole run, so it may be longer than
+    /// `len`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// assert_eq!(bm.find_run(2), Some(0..2));
+    /// assert_eq!(bm.find_run(3), Some(5..9));
+    /// assert_eq!(bm.find_run(5), None);
+    /// ```
+    pub fn find_run(&self, len: usize) -> Option<Range<usize>> {
+        self.iter_runs().find(|run| run.len() >= len)
+    }
+
+    /// Returns the first maximal run of unset bits that is at least `len` bits
+    /// long or `None` if there is no such run.
+    ///
+    /// The returned range covers the whole run, so it may be longer than
+    /// `len`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 6, 7, 8, 11]);
+    /// assert_eq!(bm.find_zero_run(2), Some(2..5));
+    /// assert_eq!(bm.find_zero_run(4), None);
+    /// ```
+    pub fn find_zero_run(&self, len: usize) -> Option<Range<usize>> {
+        self.iter_zero_runs().find(|run| run.len() >= len)
+    }
+}
+
+fn longest(runs: impl Iterator<Item = Range<usize>>) -> Option<Range<usize>> {
+    runs.fold(None, |longest: Option<Range<usize>>, run| match longest {
+        Some(longest) if longest.len() >= run.len() => Some(longest),
+        _ => Some(run),
+    })
+}
+
+/// Iterator over the maximal runs of set bits in the bitmap.
+///
+/// Yields the runs as index ranges, in ascending order.
+///
+/// Returned by [`BitMap::iter_runs()`].
+#[derive(Clone, Copy)]
+pub struct IterRuns<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: &'bitmap BitMap<BIT_COUNT, BUCKET_COUNT>,
+    pos: usize,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for IterRuns<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.bitmap.first_set_bit_from(self.pos)?;
+        let end = self.bitmap.first_unset_bit_from(start).unwrap_or(BIT_COUNT);
+        self.pos = end;
+        Some(start..end)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for IterRuns<'_, BIT_COUNT, BUCKET_COUNT>
+{
+}
+
+/// Iterator over the maximal runs of unset bits in the bitmap.
+///
+/// Yields the runs as index ranges, in ascending order.
+///
+/// Returned by [`BitMap::iter_zero_runs()`].
+#[derive(Clone, Copy)]
+pub struct IterZeroRuns<'bitmap, const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    bitmap: &'bitmap BitMap<BIT_COUNT, BUCKET_COUNT>,
+    pos: usize,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for IterZeroRuns<'_, BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.bitmap.first_unset_bit_from(self.pos)?;
+        let end = self.bitmap.first_set_bit_from(start).unwrap_or(BIT_COUNT);
+        self.pos = end;
+        Some(start..end)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for IterZeroRuns<'_, BIT_COUNT, BUCKET_COUNT>
+{
+}
@@ -0,0 +1,138 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+use core::fmt::Formatter;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Serializes human-readable formats as a string of `BIT_COUNT` binary digits
+/// with the most significant bit first and all other formats as the raw
+/// `BUCKET_COUNT` bytes.
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Serialize
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+/// Deserializes human-readable formats from a string of exactly `BIT_COUNT`
+/// binary digits (optionally prefixed with `0b`) or from a `0x`-prefixed
+/// string of exactly `BIT_COUNT.div_ceil(4)` hex digits, both with the most
+/// significant bit first. All other formats are deserialized from exactly
+/// `BUCKET_COUNT` bytes.
+///
+/// Input with bits set beyond `BIT_COUNT` is rejected.
+///
+/// # Panics
+/// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+impl<'de, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Deserialize<'de>
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BitMapVisitor {
+                human_readable: true,
+            })
+        } else {
+            deserializer.deserialize_bytes(BitMapVisitor {
+                human_readable: false,
+            })
+        }
+    }
+}
+
+struct BitMapVisitor<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    human_readable: bool,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMapVisitor<BIT_COUNT, BUCKET_COUNT> {
+    fn parse_binary(digits: &str) -> Option<BitMap<BIT_COUNT, BUCKET_COUNT>> {
+        if digits.len() != BIT_COUNT {
+            return None;
+        }
+        let mut bitmap = BitMap([0u8; BUCKET_COUNT]);
+        for (idx, digit) in digits.bytes().rev().enumerate() {
+            match digit {
+                b'0' => {}
+                b'1' => bitmap.set(idx),
+                _ => return None,
+            }
+        }
+        Some(bitmap)
+    }
+
+    fn parse_hex(digits: &str) -> Option<BitMap<BIT_COUNT, BUCKET_COUNT>> {
+        if digits.len() != BIT_COUNT.div_ceil(4) {
+            return None;
+        }
+        let mut bitmap = BitMap([0u8; BUCKET_COUNT]);
+        for (nibble_idx, digit) in digits.chars().rev().enumerate() {
+            let nibble = digit.to_digit(16)? as u8;
+            bitmap.0[nibble_idx / 2] |= nibble << (nibble_idx % 2 * 4);
+        }
+        let raw = bitmap.0;
+        bitmap.clean_unused_bits();
+        (raw == bitmap.0).then_some(bitmap)
+    }
+}
+
+impl<'de, const BIT_COUNT: usize, const BUCKET_COUNT: usize> Visitor<'de>
+    for BitMapVisitor<BIT_COUNT, BUCKET_COUNT>
+{
+    type Value = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        if self.human_readable {
+            write!(
+                f,
+                "a string of {BIT_COUNT} binary digits or a 0x-prefixed string of {} hex digits \
+                 without bits set beyond {BIT_COUNT}",
+                BIT_COUNT.div_ceil(4)
+            )
+        } else {
+            write!(
+                f,
+                "{BUCKET_COUNT} bytes without bits set beyond {BIT_COUNT}"
+            )
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let parsed = if let Some(hex) = v.strip_prefix("0x") {
+            Self::parse_hex(hex)
+        } else {
+            Self::parse_binary(v.strip_prefix("0b").unwrap_or(v))
+        };
+        parsed.ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != BUCKET_COUNT {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut bitmap = BitMap([0u8; BUCKET_COUNT]);
+        bitmap.0.copy_from_slice(v);
+        bitmap.clean_unused_bits();
+        if bitmap.0 != v {
+            return Err(E::invalid_value(Unexpected::Bytes(v), &self));
+        }
+        Ok(bitmap)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; BUCKET_COUNT];
+        for (idx, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(idx, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(BUCKET_COUNT + 1, &self));
+        }
+        self.visit_bytes(&bytes)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::bitmap::BitMap;
+use core::ops::Range;
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Inserts a bit with the given value at `idx`, moving all bits at and
+    /// above `idx` one position up.
+    ///
+    /// The bit at `BIT_COUNT - 1` is shifted out and lost.
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 4, 9]);
+    /// bm.insert_bit(2, true);
+    /// assert_eq!(bm, BitMap::from_ones_iter([1, 2, 5]));
+    /// ```
+    #[inline]
+    pub fn insert_bit(&mut self, idx: usize, value: bool) {
+        self.insert_range(idx..idx + 1, value);
+    }
+
+    /// Removes the bit at `idx` and returns its value, moving all bits above
+    /// `idx` one position down.
+    ///
+    /// The bit at `BIT_COUNT - 1` is unset afterwards.
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([1, 2, 5, 9]);
+    /// assert!(bm.remove_bit(2));
+    /// assert_eq!(bm, BitMap::from_ones_iter([1, 4, 8]));
+    /// ```
+    #[inline]
+    pub fn remove_bit(&mut self, idx: usize) -> bool {
+        let value = self.is_set(idx);
+        self.remove_range(idx..idx + 1);
+        value
+    }
+
+    /// Inserts `range.len()` bits with the given value at `range.start`,
+    /// moving all bits at and above `range.start` up by `range.len()`.
+    ///
+    /// The top `range.len()` bits are shifted out and lost. Bits below
+    /// `range.start` are untouched.
+    ///
+    /// # Panics
+    /// Panics if `range.start >= BIT_COUNT` or `range.end > BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 3, 4]);
+    /// bm.insert_range(2..5, false);
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 6, 7]));
+    /// ```
+    pub fn insert_range(&mut self, range: Range<usize>, value: bool) {
+        assert_range_in_bounds::<BIT_COUNT>(&range);
+        if range.is_empty() {
+            return;
+        }
+        let mut shifted = *self;
+        shifted.shift_left(range.len());
+        self.replace_from(range.start, &shifted);
+        if value {
+            self.set_range(range);
+        } else {
+            self.unset_range(range);
+        }
+    }
+
+    /// Removes the bits in `range`, moving all bits at and above `range.end`
+    /// down by `range.len()`.
+    ///
+    /// The top `range.len()` bits are unset afterwards. Bits below
+    /// `range.start` are untouched.
+    ///
+    /// # Panics
+    /// Panics if `range.start >= BIT_COUNT` or `range.end > BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut bm = BitMap::<10, { bucket_count(10) }>::from_ones_iter([0, 3, 6, 9]);
+    /// bm.remove_range(2..5);
+    /// assert_eq!(bm, BitMap::from_ones_iter([0, 3, 6]));
+    /// ```
+    pub fn remove_range(&mut self, range: Range<usize>) {
+        assert_range_in_bounds::<BIT_COUNT>(&range);
+        if range.is_empty() {
+            return;
+        }
+        let mut shifted = *self;
+        shifted.shift_right(range.len());
+        self.replace_from(range.start, &shifted);
+    }
+
+    /// Replaces all bits at and above `start` with those of `other`, bucket
+    /// by bucket.
+    fn replace_from(&mut self, start: usize, other: &Self) {
+        let (start_byte, start_bit) = (start / 8, start % 8);
+        let keep = !(!0u8 << start_bit);
+        self.0[start_byte] = self.0[start_byte] & keep | other.0[start_byte] & !keep;
+        self.0[start_byte + 1..].copy_from_slice(&other.0[start_byte + 1..]);
+    }
+}
+
+fn assert_range_in_bounds<const BIT_COUNT: usize>(range: &Range<usize>) {
+    assert!(
+        range.start < BIT_COUNT,
+        "Range start {} out of bounds",
+        range.start
+    );
+    assert!(
+        range.end <= BIT_COUNT,
+        "Range end {} out of bounds",
+        range.end
+    );
+}
@@ -0,0 +1,206 @@
+use crate::bitmap::{BitMap, runtime_assert_const_params};
+use core::iter::{FusedIterator, Iterator};
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns an iterator over all subsets of the set bits of `self`.
+    ///
+    /// Treating `self` as a mask, this is the `sub = (sub - 1) & mask` trick
+    /// generalized to multiple buckets: subsets are yielded in descending
+    /// numeric order, starting with `self` and ending with the empty bitmap.
+    /// The iterator yields `2^popcount` bitmaps.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mask = BitMap::<12, { bucket_count(12) }>::from_ones_iter([1, 10]);
+    /// let mut subsets = mask.subsets();
+    /// assert_eq!(subsets.next(), Some(mask));
+    /// assert_eq!(subsets.next(), Some(BitMap::from_ones_iter([10])));
+    /// assert_eq!(subsets.next(), Some(BitMap::from_ones_iter([1])));
+    /// assert_eq!(subsets.next(), Some(BitMap::new()));
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[inline]
+    pub fn subsets(&self) -> Subsets<BIT_COUNT, BUCKET_COUNT> {
+        Subsets {
+            mask: *self,
+            next: Some(*self),
+        }
+    }
+
+    /// Returns an iterator over all subsets of the set bits of `self` in
+    /// reflected Gray code order.
+    ///
+    /// Starts with the empty bitmap and consecutive subsets differ in exactly
+    /// one bit. The iterator yields `2^popcount` bitmaps.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mask = BitMap::<12, { bucket_count(12) }>::from_ones_iter([1, 10]);
+    /// let mut subsets = mask.subsets_gray();
+    /// assert_eq!(subsets.next(), Some(BitMap::new()));
+    /// assert_eq!(subsets.next(), Some(BitMap::from_ones_iter([1])));
+    /// assert_eq!(subsets.next(), Some(BitMap::from_ones_iter([1, 10])));
+    /// assert_eq!(subsets.next(), Some(BitMap::from_ones_iter([10])));
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[inline]
+    pub fn subsets_gray(&self) -> SubsetsGray<BIT_COUNT, BUCKET_COUNT> {
+        SubsetsGray {
+            mask: *self,
+            next: Some(Self([0u8; BUCKET_COUNT])),
+            odd: false,
+        }
+    }
+
+    /// Returns an iterator over all bitmaps with exactly `k` bits set, in
+    /// ascending numeric order.
+    ///
+    /// This is Gosper's hack carried across buckets. Yields a single empty
+    /// bitmap for `k == 0` and nothing for `k > BIT_COUNT`.
+    ///
+    /// # Panics
+    /// Panics if `BIT_COUNT == 0` or `BUCKET_COUNT != bucket_count(BIT_COUNT)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let mut combinations = BitMap::<3, { bucket_count(3) }>::combinations(2);
+    /// assert_eq!(combinations.next(), Some(BitMap::from_ones_iter([0, 1])));
+    /// assert_eq!(combinations.next(), Some(BitMap::from_ones_iter([0, 2])));
+    /// assert_eq!(combinations.next(), Some(BitMap::from_ones_iter([1, 2])));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    pub fn combinations(k: usize) -> Combinations<BIT_COUNT, BUCKET_COUNT> {
+        runtime_assert_const_params(BIT_COUNT, BUCKET_COUNT);
+        let next = (k <= BIT_COUNT).then(|| {
+            let mut first = Self::new();
+            first.set_range(0..k);
+            first
+        });
+        Combinations { next }
+    }
+
+    /// Subtracts one, treating the bitmap as an unsigned integer. Wraps around
+    /// the full buckets, so the caller has to clean up unused bits.
+    fn wrapping_decrement(&mut self) {
+        for byte in &mut self.0 {
+            let (decremented, borrow) = byte.overflowing_sub(1);
+            *byte = decremented;
+            if !borrow {
+                return;
+            }
+        }
+    }
+}
+
+/// Iterator over all subsets of a mask in descending numeric order.
+///
+/// Returned by [`BitMap::subsets()`].
+#[derive(Clone, Copy)]
+pub struct Subsets<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    mask: BitMap<BIT_COUNT, BUCKET_COUNT>,
+    next: Option<BitMap<BIT_COUNT, BUCKET_COUNT>>,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for Subsets<BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.first_set_bit().map(|_| {
+            let mut sub = current;
+            sub.wrapping_decrement();
+            sub.in_place_bit_and(&self.mask);
+            sub
+        });
+        Some(current)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for Subsets<BIT_COUNT, BUCKET_COUNT>
+{
+}
+
+/// Iterator over all subsets of a mask in reflected Gray code order.
+///
+/// Returned by [`BitMap::subsets_gray()`].
+#[derive(Clone, Copy)]
+pub struct SubsetsGray<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    mask: BitMap<BIT_COUNT, BUCKET_COUNT>,
+    next: Option<BitMap<BIT_COUNT, BUCKET_COUNT>>,
+    odd: bool,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for SubsetsGray<BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        // with an even number of set bits flip the lowest mask bit, otherwise
+        // flip the mask bit above the lowest set bit
+        let flip = if self.odd {
+            current
+                .first_set_bit()
+                .and_then(|low| self.mask.first_set_bit_from(low + 1))
+        } else {
+            self.mask.first_set_bit()
+        };
+        self.next = flip.map(|idx| {
+            let mut following = current;
+            following.toggle(idx);
+            following
+        });
+        self.odd = !self.odd;
+        Some(current)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for SubsetsGray<BIT_COUNT, BUCKET_COUNT>
+{
+}
+
+/// Iterator over all bitmaps with a fixed number of set bits in ascending
+/// numeric order.
+///
+/// Returned by [`BitMap::combinations()`].
+#[derive(Clone, Copy)]
+pub struct Combinations<const BIT_COUNT: usize, const BUCKET_COUNT: usize> {
+    next: Option<BitMap<BIT_COUNT, BUCKET_COUNT>>,
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> Iterator
+    for Combinations<BIT_COUNT, BUCKET_COUNT>
+{
+    type Item = BitMap<BIT_COUNT, BUCKET_COUNT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        // Gosper's hack: move the highest bit of the lowest run of ones up by
+        // one and pack the remaining bits of that run at the bottom
+        self.next = current.first_set_bit().and_then(|run_start| {
+            let run_end = current.first_unset_bit_from(run_start)?;
+            let mut following = current;
+            following.unset_range(run_start..run_end);
+            following.set(run_end);
+            following.set_range(0..run_end - run_start - 1);
+            Some(following)
+        });
+        Some(current)
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> FusedIterator
+    for Combinations<BIT_COUNT, BUCKET_COUNT>
+{
+}
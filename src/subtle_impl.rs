@@ -0,0 +1,105 @@
+use crate::bitmap::BitMap;
+use core::array::from_fn;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// Compares all buckets without returning early on the first difference.
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> ConstantTimeEq
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+/// Selects whole bitmaps bucket by bucket without branching on `choice`.
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> ConditionallySelectable
+    for BitMap<BIT_COUNT, BUCKET_COUNT>
+{
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(from_fn(|i| {
+            u8::conditional_select(&a.0[i], &b.0[i], choice)
+        }))
+    }
+}
+
+impl<const BIT_COUNT: usize, const BUCKET_COUNT: usize> BitMap<BIT_COUNT, BUCKET_COUNT> {
+    /// Returns the number of set bits in constant time.
+    ///
+    /// Counts every bucket with fixed branch-free arithmetic instead of
+    /// relying on how [`u8::count_ones`] is lowered for the target.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 7, 11]);
+    /// assert_eq!(bm.popcount_ct(), 3);
+    /// ```
+    pub fn popcount_ct(&self) -> usize {
+        self.0
+            .iter()
+            .map(|&byte| count_ones_ct(byte) as usize)
+            .sum()
+    }
+
+    /// Returns whether the bit at the given index is set as a [`Choice`]
+    /// instead of a `bool`, so the caller does not branch on it.
+    ///
+    /// Only the contents are treated as secret, `idx` is not.
+    ///
+    /// # Panics
+    /// Panics if `idx >= BIT_COUNT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// let bm = BitMap::<12, { bucket_count(12) }>::from_ones_iter([3]);
+    /// assert!(bool::from(bm.is_set_ct(3)));
+    /// assert!(!bool::from(bm.is_set_ct(4)));
+    /// ```
+    #[inline]
+    pub fn is_set_ct(&self, idx: usize) -> Choice {
+        assert!(idx < BIT_COUNT, "Bit index {idx} out of bounds");
+        Choice::from(self.0[idx / 8] >> (idx % 8) & 1)
+    }
+
+    /// Returns a new bitmap taking the bits of `other` where `mask` is set and
+    /// the bits of `self` everywhere else, in constant time.
+    ///
+    /// Same result as [`blend`], but every bit is picked with
+    /// [`u8::conditional_select`] on a [`Choice`] made from the mask bit, so
+    /// no branch or lookup depends on the contents of the mask.
+    ///
+    /// # Examples
+    /// ```
+    /// use light_bitmap::{BitMap, bucket_count};
+    ///
+    /// type Bm = BitMap<8, { bucket_count(8) }>;
+    /// let a = Bm::from_ones_iter([0, 1, 2, 3]);
+    /// let b = Bm::from_ones_iter([2, 4]);
+    /// let mask = Bm::from_ones_iter([2, 3, 4, 5]);
+    /// assert_eq!(a.blend_ct(&b, &mask), Bm::from_ones_iter([0, 1, 2, 4]));
+    /// ```
+    ///
+    /// [`blend`]: BitMap::blend
+    pub fn blend_ct(&self, other: &Self, mask: &Self) -> Self {
+        Self(from_fn(|i| {
+            let mut byte = 0;
+            for bit in 0..8 {
+                let choice = Choice::from(mask.0[i] >> bit & 1);
+                byte |= u8::conditional_select(&self.0[i], &other.0[i], choice) & 1 << bit;
+            }
+            byte
+        }))
+    }
+}
+
+/// Counts the set bits of a byte with branch-free SWAR arithmetic.
+fn count_ones_ct(byte: u8) -> u8 {
+    let pairs = byte - (byte >> 1 & 0x55);
+    let nibbles = (pairs & 0x33) + (pairs >> 2 & 0x33);
+    (nibbles + (nibbles >> 4)) & 0x0f
+}
@@ -1,6 +1,7 @@
 use super::*;
 use bitmap::{compile_assert_const_params, ones_mask, runtime_assert_const_params};
 use core::array::from_fn;
+use core::cmp::Ordering;
 use core::fmt::{self, Write};
 
 #[test]
@@ -118,7 +119,8 @@ fn test_ui() {
     t.compile_fail("tests/ui/zero_length_empty.rs");
     t.compile_fail("tests/ui/bits_buckets_mismatch_empty.rs");
     t.compile_fail("tests/ui/zero_length_full.rs");
-    t.compile_fail("tests/ui/bits_buckets_mismatch_full.rs")
+    t.compile_fail("tests/ui/bits_buckets_mismatch_full.rs");
+    t.compile_fail("tests/ui/variant_count_mismatch_empty.rs")
 }
 
 #[test]
@@ -636,6 +638,56 @@ fn test_operator_traits() {
     let mut m2 = a;
     m2 >>= 2;
     assert_eq!(m1, m2);
+
+    // bit_and_not
+    assert_eq!(a.bit_and_not(&b), a - b);
+    assert_eq!(a - b, a & !b);
+    let mut tmp = a;
+    tmp -= b;
+    assert_eq!(tmp, a - b);
+    let mut tmp = a;
+    tmp.in_place_bit_and_not(&b);
+    assert_eq!(tmp, a - b);
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn test_operator_traits_on_references() {
+    const BIT_COUNT: usize = 20;
+    let a = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 2, 5, 7, 8, 19]);
+    let b = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([1, 2, 7, 9, 13]);
+
+    macro_rules! assert_ref_binary_op {
+        ($op:tt, $assign_op:tt) => {
+            let expected = a $op b;
+            assert_eq!(&a $op &b, expected);
+            assert_eq!(a $op &b, expected);
+            assert_eq!(&a $op b, expected);
+            let mut tmp = a;
+            tmp $assign_op &b;
+            assert_eq!(tmp, expected);
+        };
+    }
+    assert_ref_binary_op!(&, &=);
+    assert_ref_binary_op!(|, |=);
+    assert_ref_binary_op!(^, ^=);
+    assert_ref_binary_op!(-, -=);
+
+    assert_eq!(!&a, !a);
+
+    let shift = 3;
+    assert_eq!(&a << shift, a << shift);
+    assert_eq!(a << &shift, a << shift);
+    assert_eq!(&a << &shift, a << shift);
+    assert_eq!(&a >> shift, a >> shift);
+    assert_eq!(a >> &shift, a >> shift);
+    assert_eq!(&a >> &shift, a >> shift);
+    let mut tmp = a;
+    tmp <<= &shift;
+    assert_eq!(tmp, a << shift);
+    let mut tmp = a;
+    tmp >>= &shift;
+    assert_eq!(tmp, a >> shift);
 }
 
 #[test]
@@ -739,6 +791,16 @@ fn test_shift_right() {
     assert_eq!(bitmap.popcount(), 0); // overflowed and cleared
 }
 
+#[test]
+fn test_shift_right_by_whole_buckets_keeps_lower_buckets() {
+    const BIT_COUNT: usize = 40;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([9, 20, 39]);
+    bitmap.shift_right(8);
+    assert_eq!(bitmap, BitMap::from_ones_iter([1, 12, 31]));
+    bitmap.shift_right(11);
+    assert_eq!(bitmap, BitMap::from_ones_iter([1, 20]));
+}
+
 #[test]
 fn test_shift_right_zero_bits() {
     const BIT_COUNT: usize = 16;
@@ -885,3 +947,1931 @@ fn debug_format_is_correct() {
     let out = buf.as_str();
     assert_eq!(out, "LSB -> 0: 10100001 8: 0 <- MSB");
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Flag {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+}
+
+impl BitIndexed for Flag {
+    const VARIANT_COUNT: usize = 10;
+
+    fn to_index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(idx: usize) -> Option<Self> {
+        use Flag::*;
+        [A, B, C, D, E, F, G, H, I, J].get(idx).copied()
+    }
+}
+
+type Flags = EnumSet<Flag, { Flag::VARIANT_COUNT }, { bucket_count(Flag::VARIANT_COUNT) }>;
+
+#[test]
+fn test_enum_set_insert_remove_contains() {
+    let mut flags = Flags::new();
+    assert!(flags.is_empty());
+
+    assert!(flags.insert(Flag::B));
+    assert!(flags.insert(Flag::J));
+    assert!(!flags.insert(Flag::B));
+    assert!(flags.contains(Flag::B));
+    assert!(flags.contains(Flag::J));
+    assert!(!flags.contains(Flag::A));
+    assert_eq!(flags.len(), 2);
+    assert!(flags.as_bitmap().is_set(9));
+
+    assert!(flags.remove(Flag::J));
+    assert!(!flags.remove(Flag::J));
+    assert_eq!(flags.len(), 1);
+
+    flags.clear();
+    assert!(flags.is_empty());
+}
+
+#[test]
+fn test_enum_set_const_and_all() {
+    const NONE: Flags = EnumSet::const_empty();
+    assert!(NONE.is_empty());
+    assert_eq!(Flags::default(), NONE);
+
+    let all = Flags::all();
+    assert_eq!(all.len(), Flag::VARIANT_COUNT);
+    assert_eq!(all.complement(), NONE);
+}
+
+#[test]
+fn test_enum_set_iter() {
+    let flags: Flags = [Flag::I, Flag::A, Flag::D].into_iter().collect();
+    let mut iter = flags.iter();
+    assert_eq!(iter.next(), Some(Flag::A));
+    assert_eq!(iter.next(), Some(Flag::D));
+    assert_eq!(iter.next(), Some(Flag::I));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    let mut extended = Flags::new();
+    extended.extend(&flags);
+    assert_eq!(extended, flags);
+}
+
+#[test]
+fn test_enum_set_operations() {
+    let a: Flags = [Flag::A, Flag::B, Flag::C].into_iter().collect();
+    let b: Flags = [Flag::C, Flag::D].into_iter().collect();
+
+    assert_eq!(
+        a | b,
+        [Flag::A, Flag::B, Flag::C, Flag::D].into_iter().collect()
+    );
+    assert_eq!(a & b, [Flag::C].into_iter().collect());
+    assert_eq!(a ^ b, [Flag::A, Flag::B, Flag::D].into_iter().collect());
+    assert_eq!(a - b, [Flag::A, Flag::B].into_iter().collect());
+    assert_eq!((!a).len(), Flag::VARIANT_COUNT - 3);
+    assert!(!(!a).contains(Flag::A));
+
+    let mut tmp = a;
+    tmp |= b;
+    assert_eq!(tmp, a.union(&b));
+    let mut tmp = a;
+    tmp &= b;
+    assert_eq!(tmp, a.intersection(&b));
+    let mut tmp = a;
+    tmp ^= b;
+    assert_eq!(tmp, a.symmetric_difference(&b));
+    let mut tmp = a;
+    tmp -= b;
+    assert_eq!(tmp, a.difference(&b));
+
+    assert!((a & b).is_subset(&a));
+    assert!(a.is_superset(&(a & b)));
+    assert!(!a.is_subset(&b));
+    assert!((a - b).is_disjoint(&b));
+    assert!(!a.is_disjoint(&b));
+}
+
+#[test]
+fn enum_set_debug_format_is_correct() {
+    let flags: Flags = [Flag::C, Flag::A].into_iter().collect();
+
+    let mut buf = Buffer::<128>::new();
+    write!(&mut buf, "{:?}", flags).unwrap();
+
+    assert_eq!(buf.as_str(), "{A, C}");
+}
+
+#[test]
+#[should_panic(expected = "BIT_COUNT must match BitIndexed::VARIANT_COUNT.")]
+fn test_enum_set_variant_count_mismatch() {
+    let _ = EnumSet::<Flag, 9, { bucket_count(9) }>::new();
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_bit_indexed() {
+    #[derive(Clone, Copy, Debug, PartialEq, BitIndexed)]
+    enum Capability {
+        Read,
+        Write = 7,
+        Execute,
+    }
+
+    assert_eq!(Capability::VARIANT_COUNT, 3);
+    assert_eq!(Capability::Read.to_index(), 0);
+    assert_eq!(Capability::Write.to_index(), 1);
+    assert_eq!(Capability::Execute.to_index(), 2);
+    assert_eq!(Capability::from_index(1), Some(Capability::Write));
+    assert_eq!(Capability::from_index(3), None);
+
+    let mut caps = EnumSet::<
+        Capability,
+        { Capability::VARIANT_COUNT },
+        { bucket_count(Capability::VARIANT_COUNT) },
+    >::new();
+    caps.insert(Capability::Execute);
+    assert_eq!(caps.iter().next(), Some(Capability::Execute));
+}
+
+fn as_u64<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bitmap: &BitMap<BIT_COUNT, BUCKET_COUNT>,
+) -> u64 {
+    bitmap
+        .0
+        .iter()
+        .rev()
+        .fold(0, |acc, byte| acc << 8 | *byte as u64)
+}
+
+#[test]
+fn test_subsets() {
+    const BIT_COUNT: usize = 20;
+    let mask = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([2, 7, 8, 17]);
+
+    let mut count = 0;
+    let mut prev = None;
+    for sub in mask.subsets() {
+        assert_eq!(sub & mask, sub);
+        if let Some(prev) = prev {
+            assert!(as_u64(&sub) < as_u64(&prev));
+        }
+        prev = Some(sub);
+        count += 1;
+    }
+    assert_eq!(count, 16);
+    assert_eq!(mask.subsets().next(), Some(mask));
+    assert_eq!(mask.subsets().last(), Some(BitMap::new()));
+}
+
+#[test]
+fn test_subsets_of_empty_and_full() {
+    const BIT_COUNT: usize = 9;
+    let empty = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let mut subsets = empty.subsets();
+    assert_eq!(subsets.next(), Some(empty));
+    assert_eq!(subsets.next(), None);
+    assert_eq!(subsets.next(), None);
+
+    let full = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+    assert_eq!(full.subsets().count(), 1 << BIT_COUNT);
+    assert!(full.subsets().all(|sub| sub.0[1] & !1 == 0));
+}
+
+#[test]
+fn test_subsets_gray() {
+    const BIT_COUNT: usize = 20;
+    let mask = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 7, 8, 13, 19]);
+
+    let mut seen = [u64::MAX; 32];
+    let mut prev: Option<BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>> = None;
+    let mut count = 0;
+    for sub in mask.subsets_gray() {
+        assert_eq!(sub & mask, sub);
+        assert!(!seen.contains(&as_u64(&sub)));
+        seen[count] = as_u64(&sub);
+        if let Some(prev) = prev {
+            assert_eq!((prev ^ sub).popcount(), 1);
+        }
+        prev = Some(sub);
+        count += 1;
+    }
+    assert_eq!(count, 32);
+
+    let empty = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let mut subsets = empty.subsets_gray();
+    assert_eq!(subsets.next(), Some(empty));
+    assert_eq!(subsets.next(), None);
+}
+
+#[test]
+fn test_combinations() {
+    const BIT_COUNT: usize = 10;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+
+    for (k, expected) in [
+        (0, 1),
+        (1, 10),
+        (3, 120),
+        (5, 252),
+        (9, 10),
+        (10, 1),
+        (11, 0),
+    ] {
+        let mut count = 0;
+        let mut prev: Option<Bm> = None;
+        for combination in Bm::combinations(k) {
+            assert_eq!(combination.popcount(), k);
+            if let Some(prev) = prev {
+                assert!(as_u64(&combination) > as_u64(&prev));
+            }
+            prev = Some(combination);
+            count += 1;
+        }
+        assert_eq!(count, expected, "Failed for k = {k}");
+    }
+
+    let mut combinations = Bm::combinations(10);
+    assert_eq!(combinations.next(), Some(Bm::with_all_set()));
+    assert_eq!(combinations.next(), None);
+    assert_eq!(combinations.next(), None);
+}
+
+#[test]
+fn test_first_bit_from() {
+    const BIT_COUNT: usize = 20;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([3, 17]);
+    assert_eq!(bitmap.first_set_bit_from(0), Some(3));
+    assert_eq!(bitmap.first_set_bit_from(3), Some(3));
+    assert_eq!(bitmap.first_set_bit_from(4), Some(17));
+    assert_eq!(bitmap.first_set_bit_from(18), None);
+    assert_eq!(bitmap.first_set_bit_from(25), None);
+
+    let full = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+    assert_eq!(full.first_unset_bit_from(0), None);
+    assert_eq!(bitmap.first_unset_bit_from(3), Some(4));
+    assert_eq!(bitmap.bit_not().first_unset_bit_from(4), Some(17));
+}
+
+#[test]
+fn test_iter_runs() {
+    const BIT_COUNT: usize = 30;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    bitmap.set_range(3..5);
+    bitmap.set_range(7..25);
+    bitmap.set(29);
+
+    let mut runs = bitmap.iter_runs();
+    assert_eq!(runs.next(), Some(3..5));
+    assert_eq!(runs.next(), Some(7..25));
+    assert_eq!(runs.next(), Some(29..30));
+    assert_eq!(runs.next(), None);
+    assert_eq!(runs.next(), None);
+
+    let mut zero_runs = bitmap.iter_zero_runs();
+    assert_eq!(zero_runs.next(), Some(0..3));
+    assert_eq!(zero_runs.next(), Some(5..7));
+    assert_eq!(zero_runs.next(), Some(25..29));
+    assert_eq!(zero_runs.next(), None);
+    assert_eq!(zero_runs.next(), None);
+
+    let full = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+    let mut runs = full.iter_runs();
+    assert_eq!(runs.next(), Some(0..BIT_COUNT));
+    assert_eq!(runs.next(), None);
+    assert_eq!(full.iter_zero_runs().next(), None);
+}
+
+#[test]
+fn test_runs_match_bits() {
+    const BIT_COUNT: usize = 45;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter(
+        (0..BIT_COUNT).filter(|idx| idx % 7 < 3 || (16..33).contains(idx)),
+    );
+
+    let mut from_runs = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    for run in bitmap.iter_runs() {
+        assert!(run.start == 0 || !bitmap.is_set(run.start - 1));
+        assert!(run.end == BIT_COUNT || !bitmap.is_set(run.end));
+        from_runs.set_range(run);
+    }
+    assert_eq!(from_runs, bitmap);
+
+    let mut from_zero_runs = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    for run in bitmap.iter_zero_runs() {
+        from_zero_runs.set_range(run);
+    }
+    assert_eq!(from_zero_runs, !bitmap);
+}
+
+#[test]
+fn test_longest_and_find_run() {
+    const BIT_COUNT: usize = 30;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    bitmap.set_range(2..6);
+    bitmap.set_range(10..14);
+    bitmap.set_range(20..27);
+
+    assert_eq!(bitmap.longest_run(), Some(20..27));
+    assert_eq!(bitmap.longest_zero_run(), Some(14..20));
+    assert_eq!(bitmap.find_run(4), Some(2..6));
+    assert_eq!(bitmap.find_run(5), Some(20..27));
+    assert_eq!(bitmap.find_run(8), None);
+    assert_eq!(bitmap.find_zero_run(3), Some(6..10));
+    assert_eq!(bitmap.find_zero_run(5), Some(14..20));
+    assert_eq!(bitmap.find_zero_run(7), None);
+
+    let empty = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    assert_eq!(empty.longest_run(), None);
+    assert_eq!(empty.longest_zero_run(), Some(0..BIT_COUNT));
+    assert_eq!(empty.find_zero_run(BIT_COUNT), Some(0..BIT_COUNT));
+}
+
+#[test]
+fn test_alloc_range_first_fit() {
+    const BIT_COUNT: usize = 40;
+    let mut pool = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    pool.set(2);
+    pool.set_range(12..14);
+
+    assert_eq!(pool.alloc_range(2, 1), Some(0..2));
+    assert_eq!(pool.alloc_range(4, 1), Some(3..7));
+    assert_eq!(pool.alloc_range(8, 8), Some(16..24));
+    assert_eq!(pool.alloc_range(3, 4), Some(8..11));
+    assert_eq!(pool.alloc_range(16, 1), Some(24..40));
+    assert_eq!(pool.alloc_range(2, 1), Some(14..16));
+    assert_eq!(pool.alloc_range(2, 1), None);
+    assert_eq!(pool.alloc_range(1, 1), Some(7..8));
+    assert_eq!(pool.popcount(), BIT_COUNT - 1);
+    assert_eq!(pool.alloc_range(BIT_COUNT + 1, 1), None);
+}
+
+#[test]
+fn test_alloc_range_best_fit() {
+    const BIT_COUNT: usize = 32;
+    let mut pool = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    pool.set_range(10..12);
+    pool.set_range(16..20);
+
+    // free runs: 0..10, 12..16, 20..32
+    assert_eq!(pool.alloc_range_best_fit(3, 1), Some(12..15));
+    assert_eq!(pool.alloc_range_best_fit(8, 1), Some(0..8));
+    assert_eq!(pool.alloc_range_best_fit(4, 8), Some(24..28));
+    assert_eq!(pool.alloc_range_best_fit(5, 1), None);
+    assert_eq!(pool.alloc_range_best_fit(4, 1), Some(20..24));
+}
+
+#[test]
+#[should_panic(expected = "Allocation length must be greater than zero.")]
+fn test_alloc_range_zero_len() {
+    let mut pool = BitMap::<8, { bucket_count(8) }>::new();
+    let _ = pool.alloc_range(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Alignment must be greater than zero.")]
+fn test_alloc_range_zero_align() {
+    let mut pool = BitMap::<8, { bucket_count(8) }>::new();
+    let _ = pool.alloc_range_best_fit(1, 0);
+}
+
+#[test]
+fn test_free_range() {
+    const BIT_COUNT: usize = 20;
+    let mut pool = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let block = pool.alloc_range(12, 1).unwrap();
+
+    assert!(!pool.free_range(10..14));
+    assert_eq!(pool.popcount(), 12);
+    assert!(pool.free_range(2..11));
+    assert_eq!(pool.popcount(), 3);
+    assert!(!pool.free_range(block.clone()));
+    assert!(pool.free_range(0..2));
+    assert!(pool.free_range(11..12));
+    assert_eq!(pool.popcount(), 0);
+    assert!(pool.free_range(5..5));
+}
+
+#[test]
+#[should_panic(expected = "Range end 21 out of bounds")]
+fn test_free_range_out_of_bounds() {
+    const BIT_COUNT: usize = 20;
+    let mut pool = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+    let _ = pool.free_range(0..21);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_readable() {
+    use serde_test::{Configure, Token, assert_de_tokens, assert_tokens};
+
+    const BIT_COUNT: usize = 10;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 2, 9]);
+    assert_tokens(&bitmap.readable(), &[Token::Str("1000000101")]);
+    assert_de_tokens(&bitmap.readable(), &[Token::Str("0b1000000101")]);
+    assert_de_tokens(&bitmap.readable(), &[Token::Str("0x205")]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_readable_invalid() {
+    use serde_test::{Readable, Token, assert_de_tokens_error};
+
+    type Bm = Readable<BitMap<10, { bucket_count(10) }>>;
+    let expected = "a string of 10 binary digits or a 0x-prefixed string of 3 hex digits without \
+                    bits set beyond 10";
+    for input in [
+        "100000010",
+        "10000001010",
+        "100000010x",
+        "0x05",
+        "0x805",
+        "0xg05",
+    ] {
+        let mut message = Buffer::<256>::new();
+        write!(
+            &mut message,
+            "invalid value: string {input:?}, expected {expected}"
+        )
+        .unwrap();
+        assert_de_tokens_error::<Bm>(&[Token::Str(input)], message.as_str());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_compact() {
+    use serde_test::{Configure, Token, assert_de_tokens, assert_tokens};
+
+    const BIT_COUNT: usize = 10;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 2, 9]);
+    assert_tokens(&bitmap.compact(), &[Token::Bytes(&[0b101, 0b10])]);
+    assert_de_tokens(
+        &bitmap.compact(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U8(0b101),
+            Token::U8(0b10),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_compact_invalid() {
+    use serde_test::{Compact, Token, assert_de_tokens_error};
+
+    type Bm = Compact<BitMap<10, { bucket_count(10) }>>;
+    assert_de_tokens_error::<Bm>(
+        &[Token::Bytes(&[0b101])],
+        "invalid length 1, expected 2 bytes without bits set beyond 10",
+    );
+    assert_de_tokens_error::<Bm>(
+        &[Token::Bytes(&[0b101, 0b110])],
+        "invalid value: byte array, expected 2 bytes without bits set beyond 10",
+    );
+    assert_de_tokens_error::<Bm>(
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U8(0),
+            Token::U8(0),
+            Token::U8(0),
+            Token::SeqEnd,
+        ],
+        "invalid length 3, expected 2 bytes without bits set beyond 10",
+    );
+}
+
+#[test]
+fn test_numeric_formatting() {
+    const BIT_COUNT: usize = 12;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 1, 3, 11]);
+
+    let mut buf = Buffer::<256>::new();
+    write!(
+        &mut buf,
+        "{bitmap} {bitmap:#} {bitmap:b} {bitmap:#b} {bitmap:o} {bitmap:#o} {bitmap:x} {bitmap:#X}"
+    )
+    .unwrap();
+    assert_eq!(
+        buf.as_str(),
+        "100000001011 0b100000001011 100000001011 0b100000001011 4013 0o4013 80b 0x80B"
+    );
+}
+
+#[test]
+fn test_numeric_formatting_empty() {
+    const BIT_COUNT: usize = 10;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+
+    let mut buf = Buffer::<128>::new();
+    write!(&mut buf, "{bitmap} {bitmap:b} {bitmap:#o} {bitmap:#x}").unwrap();
+    assert_eq!(buf.as_str(), "0000000000 0 0o0 0x0");
+}
+
+#[test]
+fn test_numeric_formatting_flags() {
+    const BIT_COUNT: usize = 9;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 4]);
+
+    let mut buf = Buffer::<256>::new();
+    write!(
+        &mut buf,
+        "[{bitmap:8x}] [{bitmap:<8x}] [{bitmap:^#8x}] [{bitmap:*>#8b}] [{bitmap:#08x}] [{bitmap:04b}] [{bitmap:>12}] [{bitmap:2}]"
+    )
+    .unwrap();
+    assert_eq!(
+        buf.as_str(),
+        "[      11] [11      ] [  0x11  ] [*0b10001] [0x000011] [10001] [   000010001] [000010001]"
+    );
+}
+
+#[test]
+fn test_grouped_formatting() {
+    const BIT_COUNT: usize = 12;
+    let bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 5, 11]);
+
+    let mut buf = Buffer::<256>::new();
+    write!(
+        &mut buf,
+        "{} {:#b} {:x} {:#o} {:>16}",
+        bitmap.grouped(4, '_'),
+        bitmap.grouped(3, '_'),
+        bitmap.grouped(1, ':'),
+        bitmap.grouped(4, '_'),
+        bitmap.grouped(4, ' ')
+    )
+    .unwrap();
+    assert_eq!(
+        buf.as_str(),
+        "1000_0010_0001 0b100_000_100_001 8:2:1 0o4041   1000 0010 0001"
+    );
+}
+
+#[test]
+fn test_numeric_formatting_wide() {
+    const BIT_COUNT: usize = 70;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    bitmap.set(69);
+
+    let mut buf = Buffer::<128>::new();
+    write!(&mut buf, "{bitmap:x} {bitmap:o}").unwrap();
+    assert_eq!(buf.as_str(), "200000000000000000 100000000000000000000000");
+}
+
+#[test]
+#[should_panic(expected = "Group size must be greater than zero.")]
+fn test_grouped_zero_size() {
+    let bitmap = BitMap::<8, { bucket_count(8) }>::new();
+    let _ = bitmap.grouped(0, '_');
+}
+
+#[test]
+fn test_from_str_formats() {
+    type Bm = BitMap<12, { bucket_count(12) }>;
+    let expected = Bm::from_ones_iter([0, 1, 3, 11]);
+
+    for input in [
+        "0b100000001011",
+        "0b1000_0000_1011",
+        "0b0000100000001011",
+        "0o4013",
+        "0x80b",
+        "0x80B",
+        "0x0_80b",
+        "100000001011",
+        "1000_0000_1011",
+        "LSB -> 0: 11010000 8: 0001 <- MSB",
+        "0,1,3,11",
+        " 0-1 , 3,11 ",
+        "11,3,0-1,1",
+    ] {
+        assert_eq!(input.parse::<Bm>(), Ok(expected), "{input}");
+    }
+    assert_eq!("".parse::<Bm>(), Ok(Bm::new()));
+    assert_eq!("0b0".parse::<Bm>(), Ok(Bm::new()));
+    assert_eq!("0-11".parse::<Bm>(), Ok(Bm::with_all_set()));
+}
+
+#[test]
+fn test_from_str_round_trip() {
+    type Bm = BitMap<70, { bucket_count(70) }>;
+    let bitmap = Bm::from_ones_iter([0, 7, 8, 9, 33, 64, 69]);
+
+    let mut buf = Buffer::<256>::new();
+    write!(&mut buf, "{bitmap}").unwrap();
+    assert_eq!(buf.as_str().parse::<Bm>(), Ok(bitmap));
+
+    macro_rules! assert_round_trip {
+        ($($fmt:literal),*) => {$(
+            let mut buf = Buffer::<256>::new();
+            write!(&mut buf, $fmt, bitmap).unwrap();
+            assert_eq!(buf.as_str().parse::<Bm>(), Ok(bitmap), "{}", $fmt);
+            let mut buf = Buffer::<256>::new();
+            write!(&mut buf, $fmt, bitmap.grouped(4, '_')).unwrap();
+            assert_eq!(buf.as_str().parse::<Bm>(), Ok(bitmap), "{}", $fmt);
+        )*};
+    }
+    assert_round_trip!("{:#}", "{:#b}", "{:#o}", "{:#x}", "{:#X}", "{:#072b}");
+
+    let mut buf = Buffer::<256>::new();
+    write!(&mut buf, "{bitmap:?}").unwrap();
+    assert_eq!(buf.as_str().parse::<Bm>(), Ok(bitmap));
+}
+
+#[test]
+fn test_from_str_errors() {
+    type Bm = BitMap<12, { bucket_count(12) }>;
+
+    for (input, position, kind) in [
+        ("0b", 2, ParseErrorKind::Empty),
+        ("0x_", 2, ParseErrorKind::Empty),
+        ("0b1021", 4, ParseErrorKind::InvalidCharacter),
+        ("0o9", 2, ParseErrorKind::InvalidCharacter),
+        ("0xg0", 2, ParseErrorKind::InvalidCharacter),
+        ("0b1000000000000", 2, ParseErrorKind::OutOfRange),
+        ("0x1000", 2, ParseErrorKind::OutOfRange),
+        ("0o10000", 2, ParseErrorKind::OutOfRange),
+        ("1,,3", 2, ParseErrorKind::Empty),
+        ("1,3,", 4, ParseErrorKind::Empty),
+        ("1, 3-", 5, ParseErrorKind::Empty),
+        ("1,a", 2, ParseErrorKind::InvalidCharacter),
+        ("1,3-x", 4, ParseErrorKind::InvalidCharacter),
+        ("1, 12", 3, ParseErrorKind::OutOfRange),
+        ("99999999999999999999999", 0, ParseErrorKind::OutOfRange),
+        ("1,9-3", 2, ParseErrorKind::InvalidRange),
+        (
+            "LSB -> 0: 11010000 8: 000 <- MSB",
+            22,
+            ParseErrorKind::LengthMismatch,
+        ),
+        (
+            "LSB -> 0: 11010000 8: 00011 <- MSB",
+            22,
+            ParseErrorKind::LengthMismatch,
+        ),
+        (
+            "LSB -> 0: 11010000 <- MSB",
+            19,
+            ParseErrorKind::LengthMismatch,
+        ),
+        (
+            "LSB -> 0: 11010000 8: 0001 16: 0 <- MSB",
+            26,
+            ParseErrorKind::LengthMismatch,
+        ),
+        (
+            "LSB -> 0: 11012000 8: 0001 <- MSB",
+            14,
+            ParseErrorKind::InvalidCharacter,
+        ),
+        (
+            "LSB -> 0: 11010000 9: 0001 <- MSB",
+            19,
+            ParseErrorKind::InvalidCharacter,
+        ),
+        (
+            "LSB -> 0: 11010000 8: 0001 <- LSB",
+            26,
+            ParseErrorKind::InvalidCharacter,
+        ),
+    ] {
+        let err = input.parse::<Bm>().unwrap_err();
+        assert_eq!((err.position(), err.kind()), (position, kind), "{input}");
+    }
+}
+
+#[test]
+fn test_parse_error_display() {
+    let err = "0b12"
+        .parse::<BitMap<8, { bucket_count(8) }>>()
+        .unwrap_err();
+    let mut buf = Buffer::<64>::new();
+    write!(&mut buf, "{err}").unwrap();
+    assert_eq!(buf.as_str(), "invalid character at position 3");
+}
+
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+#[test]
+fn test_cast_from_bytes_checked() {
+    type Bm = BitMap<12, { bucket_count(12) }>;
+
+    let bm = Bm::ref_from_bytes_checked(&[0b1000_0001, 0b0000_1000]).unwrap();
+    assert_eq!(*bm, Bm::from_ones_iter([0, 7, 11]));
+    assert_eq!(bm.popcount(), 3);
+    assert!(Bm::ref_from_bytes_checked(&[0xff, 0b0001_0000]).is_none());
+    assert!(Bm::ref_from_bytes_checked(&[0xff]).is_none());
+    assert!(Bm::ref_from_bytes_checked(&[0xff, 0x0f, 0]).is_none());
+
+    let mut bytes = [0u8, 0b1000_0000];
+    assert!(Bm::mut_from_bytes_checked(&mut bytes).is_none());
+    let mut bytes = [0u8; 2];
+    let bm = Bm::mut_from_bytes_checked(&mut bytes).unwrap();
+    bm.set_range(4..12);
+    assert_eq!(bytes, [0xf0, 0x0f]);
+
+    type Full = BitMap<16, { bucket_count(16) }>;
+    assert_eq!(
+        Full::ref_from_bytes_checked(&[0xff, 0xff]),
+        Some(&Full::with_all_set())
+    );
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck_casts() {
+    type Bm = BitMap<12, { bucket_count(12) }>;
+
+    let bm = Bm::from_ones_iter([0, 9]);
+    assert_eq!(bytemuck::bytes_of(&bm), &[0b0000_0001, 0b0000_0010]);
+    assert_eq!(bytemuck::cast::<_, Bm>([0b0000_0001u8, 0b0000_0010]), bm);
+    assert_eq!(<Bm as bytemuck::Zeroable>::zeroed(), Bm::new());
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn test_zerocopy_casts() {
+    use zerocopy::{FromBytes, FromZeros, IntoBytes};
+
+    type Bm = BitMap<12, { bucket_count(12) }>;
+
+    let bm = Bm::from_ones_iter([0, 9]);
+    assert_eq!(bm.as_bytes(), &[0b0000_0001, 0b0000_0010]);
+    assert_eq!(Bm::read_from_bytes(&[0b0000_0001, 0b0000_0010]), Ok(bm));
+    assert_eq!(Bm::new_zeroed(), Bm::new());
+}
+
+#[test]
+fn test_ord_numeric() {
+    type Bm = BitMap<20, { bucket_count(20) }>;
+    let mut bitmaps = [
+        Bm::from_ones_iter([19]),
+        Bm::from_ones_iter([
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+        ]),
+        Bm::new(),
+        Bm::from_ones_iter([8]),
+        Bm::from_ones_iter([0, 8]),
+        Bm::from_ones_iter([7]),
+    ];
+    bitmaps.sort_unstable();
+    assert_eq!(
+        bitmaps,
+        [
+            Bm::new(),
+            Bm::from_ones_iter([7]),
+            Bm::from_ones_iter([8]),
+            Bm::from_ones_iter([0, 8]),
+            Bm::from_ones_iter([
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18
+            ]),
+            Bm::from_ones_iter([19]),
+        ]
+    );
+    assert_eq!(Bm::with_all_set().cmp(&Bm::with_all_set()), Ordering::Equal);
+    assert_eq!(Bm::new().max(Bm::with_all_set()), Bm::with_all_set());
+}
+
+#[test]
+fn test_ord_matches_u32() {
+    type Bm = BitMap<32, { bucket_count(32) }>;
+    let values = [0u32, 1, 0xff, 0x100, 0x8000_0000, 0x7fff_ffff, 0x0001_0001];
+    for a in values {
+        for b in values {
+            let bm_a = Bm::from_ones_iter((0..32).filter(|idx| a >> idx & 1 == 1));
+            let bm_b = Bm::from_ones_iter((0..32).filter(|idx| b >> idx & 1 == 1));
+            assert_eq!(bm_a.cmp(&bm_b), a.cmp(&b), "{a} {b}");
+        }
+    }
+}
+
+#[test]
+fn test_partial_cmp_subset() {
+    type Bm = BitMap<20, { bucket_count(20) }>;
+    let a = Bm::from_ones_iter([1, 19]);
+    let b = Bm::from_ones_iter([1, 10, 19]);
+    let c = Bm::from_ones_iter([2, 19]);
+
+    assert_eq!(a.partial_cmp_subset(&b), Some(Ordering::Less));
+    assert_eq!(b.partial_cmp_subset(&a), Some(Ordering::Greater));
+    assert_eq!(b.partial_cmp_subset(&b), Some(Ordering::Equal));
+    assert_eq!(a.partial_cmp_subset(&c), None);
+    assert_eq!(b.partial_cmp_subset(&c), None);
+    assert_eq!(Bm::new().partial_cmp_subset(&c), Some(Ordering::Less));
+    assert_eq!(
+        Bm::with_all_set().partial_cmp_subset(&c),
+        Some(Ordering::Greater)
+    );
+
+    assert!(SubsetOrd(a) < SubsetOrd(b));
+    assert!(SubsetOrd(b) >= SubsetOrd(a));
+    assert!(SubsetOrd(a) <= SubsetOrd(a));
+    assert_eq!(SubsetOrd(a).partial_cmp(&SubsetOrd(c)), None);
+    // numeric order still relates bitmaps the inclusion order does not
+    assert!(a < c);
+}
+
+#[test]
+fn test_from_iter_usize() {
+    const BIT_COUNT: usize = 20;
+    let bitmap: BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }> =
+        [0, 9, 19, 9].into_iter().collect();
+    assert_eq!(bitmap, BitMap::from_ones_iter([0, 9, 19]));
+
+    let from_ones: BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }> =
+        bitmap.iter_ones().map(|idx| BIT_COUNT - 1 - idx).collect();
+    assert_eq!(from_ones, BitMap::from_ones_iter([0, 10, 19]));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_from_iter_usize_out_of_bounds() {
+    let _: BitMap<8, { bucket_count(8) }> = [8].into_iter().collect();
+}
+
+#[test]
+fn test_extend() {
+    const BIT_COUNT: usize = 20;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([1]);
+    bitmap.extend([4, 19]);
+    bitmap.extend(&[4, 5]);
+    assert_eq!(bitmap, BitMap::from_ones_iter([1, 4, 5, 19]));
+
+    bitmap.extend([true, false, false, false, false, false, true]);
+    assert_eq!(bitmap, BitMap::from_ones_iter([0, 1, 4, 5, 6, 19]));
+    bitmap.extend(&[false; BIT_COUNT]);
+    assert_eq!(bitmap, BitMap::from_ones_iter([0, 1, 4, 5, 6, 19]));
+    bitmap.extend(core::iter::repeat_n(&true, BIT_COUNT));
+    assert_eq!(bitmap, BitMap::with_all_set());
+}
+
+#[test]
+#[should_panic(expected = "Iterator yielded more than 8 elements")]
+fn test_extend_bool_too_long() {
+    let mut bitmap = BitMap::<8, { bucket_count(8) }>::new();
+    bitmap.extend([false; 9]);
+}
+
+#[test]
+fn test_union_and_intersection_all() {
+    type Bm = BitMap<20, { bucket_count(20) }>;
+    let bitmaps = [
+        Bm::from_ones_iter([0, 3, 9, 19]),
+        Bm::from_ones_iter([3, 9, 10, 19]),
+        Bm::from_ones_iter([1, 3, 19]),
+    ];
+
+    assert_eq!(
+        Bm::union_all(bitmaps.iter()),
+        Bm::from_ones_iter([0, 1, 3, 9, 10, 19])
+    );
+    assert_eq!(
+        Bm::union_all(bitmaps),
+        Bm::from_ones_iter([0, 1, 3, 9, 10, 19])
+    );
+    assert_eq!(
+        Bm::intersection_all(bitmaps.iter()),
+        Bm::from_ones_iter([3, 19])
+    );
+    assert_eq!(
+        Bm::intersection_all(bitmaps.iter().take(2)),
+        Bm::from_ones_iter([3, 9, 19])
+    );
+    assert_eq!(Bm::union_all(bitmaps.iter().take(0)), Bm::new());
+    assert_eq!(
+        Bm::intersection_all(bitmaps.iter().take(0)),
+        Bm::with_all_set()
+    );
+}
+
+#[test]
+fn test_read_write_bits() {
+    const BIT_COUNT: usize = 70;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+    bitmap.write_bits(3, 64, 0x0123_4567_89ab_cdef);
+    assert_eq!(bitmap.read_bits(3, 64), 0x0123_4567_89ab_cdef);
+    assert_eq!(bitmap.read_bits(0, 3), 0b111);
+    assert_eq!(bitmap.read_bits(67, 3), 0b111);
+    assert_eq!(bitmap.read_bits(3, 4), 0xf);
+    assert_eq!(bitmap.read_bits(63, 7), 0b1110000);
+
+    bitmap.write_bits(6, 5, 0);
+    assert_eq!(bitmap.read_bits(0, 16), 0b0110_1000_0011_1111);
+    assert_eq!(bitmap.read_bits(16, 1), 0);
+}
+
+#[test]
+fn test_bit_writer_reader_round_trip() {
+    const BIT_COUNT: usize = 512;
+    for order in [BitOrder::Lsb0, BitOrder::Msb0] {
+        let mut frame = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::with_all_set();
+        let mut writer = BitWriter::new(&mut frame, order);
+        writer.write_bits(0x1f, 5).unwrap();
+        writer.write_bool(false).unwrap();
+        writer.write_bits(u64::MAX, 64).unwrap();
+        writer.write_signed(-1, 7).unwrap();
+        writer.write_signed(i64::MIN, 64).unwrap();
+        writer.write_signed(63, 7).unwrap();
+        writer.write_signed(-64, 7).unwrap();
+        writer.skip(10).unwrap();
+        writer.write_bits(0x0123_4567_89ab_cdef, 61).unwrap();
+        assert_eq!(writer.position(), 5 + 1 + 64 + 7 + 64 + 7 + 7 + 10 + 61);
+        assert_eq!(writer.remaining(), BIT_COUNT - writer.position());
+
+        let mut reader = BitReader::new(&frame, order);
+        assert_eq!(reader.read_bits(5), Ok(0x1f));
+        assert_eq!(reader.read_bool(), Ok(false));
+        assert_eq!(reader.read_bits(64), Ok(u64::MAX));
+        assert_eq!(reader.read_signed(7), Ok(-1));
+        assert_eq!(reader.read_signed(64), Ok(i64::MIN));
+        assert_eq!(reader.read_signed(7), Ok(63));
+        assert_eq!(reader.read_signed(7), Ok(-64));
+        assert_eq!(reader.read_bits(10), Ok(0x3ff));
+        assert_eq!(reader.read_bits(61), Ok(0x0123_4567_89ab_cdef));
+        assert_eq!(reader.position(), 5 + 1 + 64 + 7 + 64 + 7 + 7 + 10 + 61);
+    }
+}
+
+#[test]
+fn test_bit_order_layout() {
+    const BIT_COUNT: usize = 12;
+    let mut lsb0 = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let mut writer = BitWriter::new(&mut lsb0, BitOrder::Lsb0);
+    writer.write_bool(true).unwrap();
+    writer.write_bits(0b1100_0001, 8).unwrap();
+    assert_eq!(lsb0, BitMap::from_ones_iter([0, 1, 7, 8]));
+
+    let mut msb0 = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let mut writer = BitWriter::new(&mut msb0, BitOrder::Msb0);
+    writer.write_bool(true).unwrap();
+    writer.write_bits(0b1100_0001, 8).unwrap();
+    assert_eq!(msb0, BitMap::from_ones_iter([0, 1, 2, 8]));
+
+    let mut reader = BitReader::new(&msb0, BitOrder::Lsb0);
+    assert_eq!(reader.read_bits(9), Ok(0b1_0000_0111));
+}
+
+#[test]
+fn test_bit_stream_errors() {
+    const BIT_COUNT: usize = 10;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+    let mut writer = BitWriter::new(&mut bitmap, BitOrder::Lsb0);
+    assert_eq!(
+        writer.write_bits(8, 3),
+        Err(BitStreamError::ValueOutOfRange)
+    );
+    assert_eq!(
+        writer.write_signed(4, 3),
+        Err(BitStreamError::ValueOutOfRange)
+    );
+    assert_eq!(
+        writer.write_signed(-5, 3),
+        Err(BitStreamError::ValueOutOfRange)
+    );
+    assert_eq!(writer.write_bits(0, 11), Err(BitStreamError::OutOfSpace));
+    assert_eq!(writer.skip(11), Err(BitStreamError::OutOfSpace));
+    assert_eq!(writer.position(), 0);
+    writer.write_bits(0x3ff, 10).unwrap();
+    assert_eq!(writer.write_bool(true), Err(BitStreamError::OutOfSpace));
+    assert_eq!(bitmap, BitMap::with_all_set());
+
+    let mut reader = BitReader::new(&bitmap, BitOrder::Msb0);
+    reader.skip(4).unwrap();
+    assert_eq!(reader.read_bits(7), Err(BitStreamError::OutOfSpace));
+    assert_eq!(reader.read_signed(6), Ok(-1));
+    assert_eq!(reader.read_bool(), Err(BitStreamError::OutOfSpace));
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Bit count 65 must be between 1 and 64.")]
+fn test_bit_writer_invalid_bit_count() {
+    let mut bitmap = BitMap::<128, { bucket_count(128) }>::new();
+    let _ = BitWriter::new(&mut bitmap, BitOrder::Lsb0).write_bits(0, 65);
+}
+
+#[test]
+fn test_extract_deposit_matches_naive() {
+    const BIT_COUNT: usize = 37;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+
+    // xorshift to get reproducible pseudo-random patterns without dependencies
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Bm::from_ones_iter((0..BIT_COUNT).filter(|idx| state >> idx & 1 == 1))
+    };
+
+    for _ in 0..200 {
+        let (bitmap, mask) = (next(), next());
+
+        let expected_extract = Bm::from_ones_iter(
+            mask.iter_ones()
+                .enumerate()
+                .filter_map(|(k, idx)| bitmap.is_set(idx).then_some(k)),
+        );
+        assert_eq!(bitmap.extract(&mask), expected_extract);
+
+        let expected_deposit = Bm::from_ones_iter(
+            mask.iter_ones()
+                .enumerate()
+                .filter_map(|(k, idx)| bitmap.is_set(k).then_some(idx)),
+        );
+        assert_eq!(bitmap.deposit(&mask), expected_deposit);
+
+        assert_eq!(bitmap.extract(&mask).deposit(&mask), bitmap & mask);
+    }
+}
+
+#[test]
+fn test_extract_deposit_edge_cases() {
+    const BIT_COUNT: usize = 20;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let bitmap = Bm::from_ones_iter([0, 5, 8, 19]);
+
+    assert_eq!(bitmap.extract(&Bm::with_all_set()), bitmap);
+    assert_eq!(bitmap.deposit(&Bm::with_all_set()), bitmap);
+    assert_eq!(bitmap.extract(&Bm::new()), Bm::new());
+    assert_eq!(bitmap.deposit(&Bm::new()), Bm::new());
+    assert_eq!(
+        Bm::with_all_set().deposit(&bitmap),
+        bitmap,
+        "depositing all ones reproduces the mask"
+    );
+    assert_eq!(
+        Bm::with_all_set().extract(&bitmap),
+        Bm::from_ones_iter(0..4),
+        "extracting all ones yields popcount low bits"
+    );
+}
+
+#[test]
+fn test_extract_deposit_const() {
+    const BIT_COUNT: usize = 16;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    const MASK: Bm = {
+        let mut mask = Bm::const_empty();
+        mask.0[0] = 0b1010_1010;
+        mask.0[1] = 0b0000_0011;
+        mask
+    };
+    const PACKED: Bm = Bm::const_full().extract(&MASK);
+    const SPREAD: Bm = PACKED.deposit(&MASK);
+    assert_eq!(PACKED, Bm::from_ones_iter(0..6));
+    assert_eq!(SPREAD, MASK);
+}
+
+#[test]
+fn test_reverse_bits() {
+    macro_rules! test_reverse_by_bit_count {
+        ($($bit_count:literal),*) => {$(
+            let ones = [0, 1, $bit_count / 2, $bit_count - 1];
+            let mut bitmap = BitMap::<$bit_count, { bucket_count($bit_count) }>::from_ones_iter(ones);
+            bitmap.reverse_bits();
+            assert_eq!(
+                bitmap,
+                BitMap::from_ones_iter(ones.map(|idx| $bit_count - 1 - idx)),
+                "{}",
+                $bit_count
+            );
+            bitmap.reverse_bits();
+            assert_eq!(bitmap, BitMap::from_ones_iter(ones));
+
+            let mut full = BitMap::<$bit_count, { bucket_count($bit_count) }>::with_all_set();
+            full.reverse_bits();
+            assert_eq!(full, BitMap::with_all_set());
+        )*};
+    }
+    test_reverse_by_bit_count!(2, 7, 8, 9, 15, 16, 17, 63, 64, 65, 100);
+}
+
+#[test]
+fn test_swap() {
+    const BIT_COUNT: usize = 20;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::from_ones_iter([0, 19]);
+    bitmap.swap(0, 19);
+    assert_eq!(bitmap, BitMap::from_ones_iter([0, 19]));
+    bitmap.swap(0, 10);
+    assert_eq!(bitmap, BitMap::from_ones_iter([10, 19]));
+    bitmap.swap(5, 5);
+    bitmap.swap(10, 10);
+    assert_eq!(bitmap, BitMap::from_ones_iter([10, 19]));
+}
+
+#[test]
+#[should_panic(expected = "Bit index 20 out of bounds")]
+fn test_swap_out_of_bounds() {
+    let mut bitmap = BitMap::<20, { bucket_count(20) }>::new();
+    bitmap.swap(3, 20);
+}
+
+#[test]
+fn test_swap_ranges() {
+    const BIT_COUNT: usize = 150;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let mut bitmap = Bm::new();
+    bitmap.set_range(0..70);
+    bitmap.swap_ranges(0..70, 75..145);
+    let mut expected = Bm::new();
+    expected.set_range(75..145);
+    assert_eq!(bitmap, expected);
+
+    let mut bitmap = Bm::from_ones_iter([3, 12, 100, 149]);
+    bitmap.swap_ranges(100..150, 3..53);
+    assert_eq!(bitmap, Bm::from_ones_iter([3, 52, 100, 109]));
+    bitmap.swap_ranges(5..5, 5..5);
+    assert_eq!(bitmap, Bm::from_ones_iter([3, 52, 100, 109]));
+}
+
+#[test]
+#[should_panic(expected = "Ranges must not overlap.")]
+fn test_swap_ranges_overlapping() {
+    let mut bitmap = BitMap::<20, { bucket_count(20) }>::new();
+    bitmap.swap_ranges(0..5, 4..9);
+}
+
+#[test]
+#[should_panic(expected = "Ranges must have the same length.")]
+fn test_swap_ranges_length_mismatch() {
+    let mut bitmap = BitMap::<20, { bucket_count(20) }>::new();
+    bitmap.swap_ranges(0..5, 10..16);
+}
+
+#[test]
+fn test_permute() {
+    const BIT_COUNT: usize = 10;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let reverse_table: [usize; BIT_COUNT] = from_fn(|idx| BIT_COUNT - 1 - idx);
+    let rotate_table: [usize; BIT_COUNT] = from_fn(|idx| (idx + 3) % BIT_COUNT);
+    let original = Bm::from_ones_iter([0, 2, 3, 9]);
+
+    let mut reversed = original;
+    reversed.permute(&reverse_table);
+    let mut expected = original;
+    expected.reverse_bits();
+    assert_eq!(reversed, expected);
+
+    let mut rotated = original;
+    rotated.permute(&rotate_table);
+    let mut expected = original;
+    expected.rotate_left(3);
+    assert_eq!(rotated, expected);
+
+    let rotation =
+        Permutation::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new(rotate_table).unwrap();
+    assert_eq!(rotation.as_table(), &rotate_table);
+    let mut bitmap = original;
+    for _ in 0..BIT_COUNT {
+        bitmap.apply_permutation(&rotation);
+    }
+    assert_eq!(bitmap, original);
+    bitmap.apply_permutation(&rotation);
+    bitmap.apply_permutation(&rotation.inverse());
+    assert_eq!(bitmap, original);
+    bitmap.apply_permutation(&Permutation::identity());
+    assert_eq!(bitmap, original);
+}
+
+#[test]
+fn test_permutation_validation() {
+    type Perm = Permutation<4, { bucket_count(4) }>;
+    assert!(Perm::new([3, 2, 1, 0]).is_some());
+    assert!(Perm::new([0, 1, 2, 2]).is_none());
+    assert!(Perm::new([0, 1, 2, 4]).is_none());
+    assert_eq!(Perm::identity().as_table(), &[0, 1, 2, 3]);
+    assert_eq!(
+        Perm::new([1, 2, 3, 0]).unwrap().inverse().as_table(),
+        &[3, 0, 1, 2]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Table must be a permutation of 0..BIT_COUNT.")]
+fn test_permute_invalid_table() {
+    let mut bitmap = BitMap::<4, { bucket_count(4) }>::new();
+    bitmap.permute(&[0, 0, 1, 2]);
+}
+
+#[test]
+fn test_insert_remove_bit_matches_naive() {
+    const BIT_COUNT: usize = 29;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let original = Bm::from_ones_iter([0, 3, 7, 8, 9, 15, 16, 23, 28]);
+
+    for idx in 0..BIT_COUNT {
+        for value in [false, true] {
+            let mut inserted = original;
+            inserted.insert_bit(idx, value);
+            let expected: Bm = (0..BIT_COUNT)
+                .map(|i| match i.cmp(&idx) {
+                    Ordering::Less => original.is_set(i),
+                    Ordering::Equal => value,
+                    Ordering::Greater => original.is_set(i - 1),
+                })
+                .collect();
+            assert_eq!(inserted, expected, "insert {value} at {idx}");
+        }
+
+        let mut removed = original;
+        assert_eq!(removed.remove_bit(idx), original.is_set(idx));
+        let expected: Bm = (0..BIT_COUNT)
+            .map(|i| i + 1 < BIT_COUNT && original.is_set(if i < idx { i } else { i + 1 }))
+            .collect();
+        assert_eq!(removed, expected, "remove at {idx}");
+    }
+}
+
+#[test]
+fn test_insert_remove_range() {
+    const BIT_COUNT: usize = 40;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let original = Bm::from_ones_iter([1, 5, 12, 20, 33, 39]);
+
+    let mut bitmap = original;
+    bitmap.insert_range(4..17, true);
+    let mut expected = Bm::from_ones_iter([1, 18, 25, 33]);
+    expected.set_range(4..17);
+    assert_eq!(bitmap, expected);
+    bitmap.remove_range(4..17);
+    assert_eq!(bitmap, Bm::from_ones_iter([1, 5, 12, 20]));
+
+    let mut bitmap = original;
+    bitmap.remove_range(0..40);
+    assert_eq!(bitmap, Bm::new());
+
+    let mut bitmap = original;
+    bitmap.insert_range(0..40, true);
+    assert_eq!(bitmap, Bm::with_all_set());
+
+    let mut bitmap = original;
+    bitmap.insert_range(7..7, true);
+    bitmap.remove_range(7..7);
+    assert_eq!(bitmap, original);
+}
+
+#[test]
+#[should_panic(expected = "Range end 41 out of bounds")]
+fn test_remove_range_out_of_bounds() {
+    let mut bitmap = BitMap::<40, { bucket_count(40) }>::new();
+    bitmap.remove_range(30..41);
+}
+
+#[test]
+fn test_resize() {
+    let bitmap = BitMap::<20, { bucket_count(20) }>::from_ones_iter([0, 7, 8, 19]);
+    assert_eq!(
+        bitmap.resize::<70, { bucket_count(70) }>(),
+        BitMap::from_ones_iter([0, 7, 8, 19])
+    );
+    assert_eq!(
+        bitmap.resize::<19, { bucket_count(19) }>(),
+        BitMap::from_ones_iter([0, 7, 8])
+    );
+    assert_eq!(
+        bitmap.resize::<3, { bucket_count(3) }>(),
+        BitMap::from_ones_iter([0])
+    );
+    assert_eq!(bitmap.resize::<20, { bucket_count(20) }>(), bitmap);
+
+    assert_eq!(bitmap.try_narrow::<19, { bucket_count(19) }>(), None);
+    assert_eq!(
+        bitmap.try_narrow::<20, { bucket_count(20) }>(),
+        Some(bitmap)
+    );
+    assert_eq!(
+        bitmap.try_narrow::<64, { bucket_count(64) }>(),
+        Some(BitMap::from_ones_iter([0, 7, 8, 19]))
+    );
+}
+
+#[test]
+fn test_concat_split_at() {
+    type Low = BitMap<13, { bucket_count(13) }>;
+    type High = BitMap<11, { bucket_count(11) }>;
+    type Joined = BitMap<24, { bucket_count(24) }>;
+    let low = Low::from_ones_iter([0, 5, 12]);
+    let high = High::from_ones_iter([0, 3, 10]);
+
+    let joined = Joined::concat(&low, &high);
+    assert_eq!(joined, Joined::from_ones_iter([0, 5, 12, 13, 16, 23]));
+    let (split_low, split_high): (Low, High) = joined.split_at();
+    assert_eq!((split_low, split_high), (low, high));
+
+    let aligned = Joined::concat(
+        &BitMap::<16, 2>::from_ones_iter([15]),
+        &BitMap::<8, 1>::with_all_set(),
+    );
+    assert_eq!(
+        aligned,
+        Joined::from_ones_iter([15, 16, 17, 18, 19, 20, 21, 22, 23])
+    );
+    let (_, top): (BitMap<16, 2>, BitMap<8, 1>) = aligned.split_at();
+    assert_eq!(top, BitMap::with_all_set());
+
+    let full = Joined::with_all_set();
+    let (a, b): (BitMap<3, 1>, BitMap<21, 3>) = full.split_at();
+    assert_eq!((a.popcount(), b.popcount()), (3, 21));
+    assert_eq!(Joined::concat(&a, &b), full);
+}
+
+#[test]
+#[should_panic(expected = "BIT_COUNT must be the sum of both input BIT_COUNTs.")]
+fn test_concat_width_mismatch() {
+    let bitmap = BitMap::<8, 1>::new();
+    BitMap::<15, { bucket_count(15) }>::concat(&bitmap, &bitmap);
+}
+
+#[test]
+fn test_copy_within() {
+    const BIT_COUNT: usize = 150;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let original: Bm = (0..BIT_COUNT).map(|i| i % 3 == 0 || i % 7 == 2).collect();
+
+    for (src, dest) in [
+        (0..150, 0),
+        (0..100, 50),
+        (50..150, 0),
+        (3..77, 5),
+        (5..79, 3),
+        (10..11, 149),
+        (17..17, 150),
+        (1..140, 9),
+    ] {
+        let mut bitmap = original;
+        bitmap.copy_within(src.clone(), dest);
+        let expected: Bm = (0..BIT_COUNT)
+            .map(|i| {
+                if (dest..dest + src.len()).contains(&i) {
+                    original.is_set(src.start + i - dest)
+                } else {
+                    original.is_set(i)
+                }
+            })
+            .collect();
+        assert_eq!(bitmap, expected, "copy {src:?} to {dest}");
+    }
+}
+
+#[test]
+fn test_copy_range_from() {
+    let small = BitMap::<12, { bucket_count(12) }>::from_ones_iter([0, 1, 5, 11]);
+    let mut large = BitMap::<100, { bucket_count(100) }>::with_all_set();
+    large.copy_range_from(&small, 1..12, 85);
+    let expected: BitMap<100, { bucket_count(100) }> = (0..100)
+        .map(|i| !(85..96).contains(&i) || small.is_set(i - 84))
+        .collect();
+    assert_eq!(large, expected);
+
+    let mut small = BitMap::<12, { bucket_count(12) }>::new();
+    small.copy_range_from(&large, 81..93, 0);
+    assert_eq!(small, BitMap::from_ones_iter([0, 1, 2, 3, 4, 8]));
+}
+
+#[test]
+#[should_panic(expected = "Destination end 11 out of bounds")]
+fn test_copy_within_dest_out_of_bounds() {
+    let mut bitmap = BitMap::<10, { bucket_count(10) }>::new();
+    bitmap.copy_within(0..4, 7);
+}
+
+#[test]
+fn test_blend_assign_masked() {
+    const BIT_COUNT: usize = 21;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let a: Bm = (0..BIT_COUNT).map(|i| i % 2 == 0).collect();
+    let b: Bm = (0..BIT_COUNT).map(|i| i % 3 == 0).collect();
+    let mask: Bm = (0..BIT_COUNT).map(|i| (5..17).contains(&i)).collect();
+
+    let expected: Bm = (0..BIT_COUNT)
+        .map(|i| {
+            if mask.is_set(i) {
+                b.is_set(i)
+            } else {
+                a.is_set(i)
+            }
+        })
+        .collect();
+    assert_eq!(a.blend(&b, &mask), expected);
+    assert_eq!(a.blend(&b, &Bm::new()), a);
+    assert_eq!(a.blend(&b, &Bm::with_all_set()), b);
+
+    let mut assigned = a;
+    assigned.assign_masked(&mask, &b);
+    assert_eq!(assigned, expected);
+
+    let mut bitmap = a;
+    bitmap.set_where(&mask);
+    assert_eq!(bitmap, a | mask);
+    bitmap.clear_where(&mask);
+    assert_eq!(bitmap, a - mask);
+}
+
+#[test]
+fn test_retain() {
+    const BIT_COUNT: usize = 30;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let original = Bm::from_ones_iter([0, 3, 9, 10, 17, 24, 29]);
+
+    let mut visited = Bm::new();
+    let mut bitmap = original;
+    bitmap.retain(|idx| {
+        assert!(!visited.toggle(idx));
+        idx % 3 == 0
+    });
+    assert_eq!(visited, Bm::with_all_set());
+    assert_eq!(bitmap, Bm::from_ones_iter([0, 3, 9, 24]));
+
+    let mut visited = Bm::new();
+    let mut bitmap = original;
+    bitmap.retain_ones(|idx| {
+        assert!(!visited.toggle(idx));
+        idx % 3 == 0
+    });
+    assert_eq!(visited, original);
+    assert_eq!(bitmap, Bm::from_ones_iter([0, 3, 9, 24]));
+
+    bitmap.retain_ones(|_| false);
+    assert_eq!(bitmap, Bm::new());
+}
+
+#[test]
+fn test_test_and_modify() {
+    const BIT_COUNT: usize = 35;
+    let mut bitmap = BitMap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+
+    assert!(!bitmap.test_and_set(34));
+    assert!(bitmap.test_and_set(34));
+    assert!(bitmap.is_set(34));
+    assert!(bitmap.test_and_unset(34));
+    assert!(!bitmap.test_and_unset(34));
+    assert!(!bitmap.is_set(34));
+
+    assert!(!bitmap.replace(9, true));
+    assert!(bitmap.replace(9, true));
+    assert!(bitmap.replace(9, false));
+    assert!(!bitmap.replace(9, false));
+    assert_eq!(bitmap.popcount(), 0);
+
+    bitmap.set_range(0..BIT_COUNT);
+    assert!(bitmap.replace(17, false));
+    assert_eq!(bitmap.popcount(), BIT_COUNT - 1);
+}
+
+#[test]
+#[should_panic(expected = "Bit index 35 out of bounds")]
+fn test_test_and_set_out_of_bounds() {
+    let mut bitmap = BitMap::<35, { bucket_count(35) }>::new();
+    bitmap.test_and_set(35);
+}
+
+#[test]
+fn test_swap_with_take() {
+    type Bm = BitMap<20, { bucket_count(20) }>;
+    let mut a = Bm::from_ones_iter([0, 19]);
+    let mut b = Bm::with_all_set();
+    a.swap_with(&mut b);
+    assert_eq!(a, Bm::with_all_set());
+    assert_eq!(b, Bm::from_ones_iter([0, 19]));
+
+    assert_eq!(a.take(), Bm::with_all_set());
+    assert_eq!(a, Bm::new());
+    assert_eq!(a.take(), Bm::new());
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn test_subtle() {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    const BIT_COUNT: usize = 21;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let a: Bm = (0..BIT_COUNT).map(|i| i % 2 == 0).collect();
+    let b: Bm = (0..BIT_COUNT).map(|i| i % 3 == 0).collect();
+
+    assert!(bool::from(a.ct_eq(&a)));
+    assert!(!bool::from(a.ct_eq(&b)));
+    assert_eq!(Bm::conditional_select(&a, &b, Choice::from(0)), a);
+    assert_eq!(Bm::conditional_select(&a, &b, Choice::from(1)), b);
+
+    for byte in 0..=u8::MAX {
+        let bitmap = BitMap::<8, 1>::from_ones_iter((0..8).filter(|i| byte >> i & 1 != 0));
+        assert_eq!(bitmap.popcount_ct(), byte.count_ones() as usize);
+    }
+    assert_eq!(Bm::with_all_set().popcount_ct(), BIT_COUNT);
+
+    for idx in 0..BIT_COUNT {
+        assert_eq!(bool::from(a.is_set_ct(idx)), a.is_set(idx));
+    }
+
+    let mask = Bm::from_ones_iter([1, 2, 3, 17, 20]);
+    assert_eq!(a.blend_ct(&b, &mask), a.blend(&b, &mask));
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn test_blend_ct_matches_blend() {
+    const BIT_COUNT: usize = 45;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+
+    // xorshift to get reproducible pseudo-random patterns without dependencies
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Bm::from_ones_iter((0..BIT_COUNT).filter(|idx| state >> idx & 1 == 1))
+    };
+
+    for _ in 0..200 {
+        let (a, b, mask) = (next(), next(), next());
+        assert_eq!(a.blend_ct(&b, &mask), a.blend(&b, &mask));
+    }
+    let (a, b) = (next(), next());
+    assert_eq!(a.blend_ct(&b, &Bm::new()), a);
+    assert_eq!(a.blend_ct(&b, &Bm::with_all_set()), b);
+}
+
+#[test]
+fn test_bulk_ops_large() {
+    // spans several 32 bucket vectors plus a remainder
+    const BIT_COUNT: usize = 8 * 100 + 5;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let a: Bm = (0..BIT_COUNT).map(|i| i % 3 == 0 || i % 257 < 9).collect();
+    let b: Bm = (0..BIT_COUNT)
+        .map(|i| i % 5 == 0 || (300..700).contains(&i))
+        .collect();
+
+    let expected_and: Bm = (0..BIT_COUNT).map(|i| a.is_set(i) && b.is_set(i)).collect();
+    let expected_or: Bm = (0..BIT_COUNT).map(|i| a.is_set(i) || b.is_set(i)).collect();
+    let expected_xor: Bm = (0..BIT_COUNT).map(|i| a.is_set(i) != b.is_set(i)).collect();
+    let expected_and_not: Bm = (0..BIT_COUNT)
+        .map(|i| a.is_set(i) && !b.is_set(i))
+        .collect();
+    assert_eq!(a & b, expected_and);
+    assert_eq!(a | b, expected_or);
+    assert_eq!(a ^ b, expected_xor);
+    assert_eq!(a - b, expected_and_not);
+
+    let count = (0..BIT_COUNT).filter(|&i| a.is_set(i)).count();
+    assert_eq!(a.popcount(), count);
+    assert_eq!(a.iter_ones().count(), count);
+    assert!(a.iter_ones().eq((0..BIT_COUNT).filter(|&i| a.is_set(i))));
+
+    let sparse = Bm::from_ones_iter([0, 255, 256, 511, 700, BIT_COUNT - 1]);
+    assert!(
+        sparse
+            .iter_ones()
+            .eq([0, 255, 256, 511, 700, BIT_COUNT - 1])
+    );
+    assert_eq!(sparse.popcount(), 6);
+    let last = Bm::from_ones_iter([BIT_COUNT - 1]);
+    assert_eq!(last.first_set_bit(), Some(BIT_COUNT - 1));
+    assert!(last.iter_ones().eq([BIT_COUNT - 1]));
+    assert_eq!(Bm::new().iter_ones().next(), None);
+    assert_eq!(Bm::new().first_set_bit(), None);
+}
+
+#[test]
+fn test_rle_roundtrip() {
+    const BIT_COUNT: usize = 1000;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let mut buf = [0u8; Bm::max_rle_len()];
+
+    let mut long_runs = Bm::new();
+    long_runs.set_range(0..130);
+    long_runs.set_range(500..999);
+    let alternating: Bm = (0..BIT_COUNT).map(|i| i % 2 == 1).collect();
+    for bitmap in [
+        Bm::new(),
+        Bm::with_all_set(),
+        long_runs,
+        alternating,
+        !alternating,
+        Bm::from_ones_iter([BIT_COUNT - 1]),
+    ] {
+        let len = bitmap.encode_rle(&mut buf).unwrap();
+        assert_eq!(Bm::decode_rle(&buf[..len]), Ok(bitmap));
+
+        let mut decoder = RleDecoder::<BIT_COUNT, { bucket_count(BIT_COUNT) }>::new();
+        for byte in &buf[..len] {
+            assert!(!decoder.is_complete());
+            decoder.feed(core::slice::from_ref(byte)).unwrap();
+        }
+        assert_eq!(decoder.finish(), Ok(bitmap));
+
+        assert_eq!(
+            bitmap.encode_rle(&mut buf[..len - 1]),
+            Err(CodecError::BufferTooSmall)
+        );
+    }
+
+    // worst case fills the whole upper bound
+    assert_eq!((!alternating).encode_rle(&mut buf), Ok(Bm::max_rle_len()));
+    assert_eq!(Bm::new().encode_rle(&mut buf), Ok(2));
+    assert_eq!(&buf[..2], &[0xe8, 0x07]);
+    assert_eq!(long_runs.encode_rle(&mut buf), Ok(8));
+    assert_eq!(&buf[..8], &[0, 0x82, 0x01, 0xf2, 0x02, 0xf3, 0x03, 1]);
+}
+
+#[test]
+fn test_rle_invalid() {
+    type Bm = BitMap<10, { bucket_count(10) }>;
+    assert_eq!(Bm::decode_rle(&[]), Err(CodecError::UnexpectedEnd));
+    assert_eq!(Bm::decode_rle(&[0x8a]), Err(CodecError::UnexpectedEnd));
+    assert_eq!(Bm::decode_rle(&[11]), Err(CodecError::InvalidRun));
+    assert_eq!(Bm::decode_rle(&[3, 0, 7]), Err(CodecError::InvalidRun));
+    assert_eq!(Bm::decode_rle(&[0, 0, 10]), Err(CodecError::InvalidRun));
+    assert_eq!(Bm::decode_rle(&[10, 0]), Err(CodecError::TrailingBytes));
+    assert_eq!(
+        Bm::decode_rle(&[0x8a, 0x00]),
+        Err(CodecError::InvalidVarint)
+    );
+    assert_eq!(
+        Bm::decode_rle(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+        Err(CodecError::InvalidVarint)
+    );
+    assert_eq!(Bm::decode_rle(&[0, 10]), Ok(Bm::with_all_set()));
+}
+
+#[test]
+fn test_index_list() {
+    const BIT_COUNT: usize = 1000;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let mut buf = [0u8; BIT_COUNT];
+
+    for bitmap in [
+        Bm::new(),
+        Bm::with_all_set(),
+        Bm::from_ones_iter([0]),
+        Bm::from_ones_iter([BIT_COUNT - 1]),
+        Bm::from_ones_iter([1, 2, 127, 128, 256, 999]),
+    ] {
+        let len = bitmap.encode_index_list(&mut buf).unwrap();
+        assert_eq!(Bm::decode_index_list(&buf[..len]), Ok(bitmap));
+    }
+    assert_eq!(
+        Bm::with_all_set().encode_index_list(&mut buf),
+        Ok(BIT_COUNT)
+    );
+    assert_eq!(Bm::new().encode_index_list(&mut []), Ok(0));
+    assert_eq!(
+        Bm::from_ones_iter([BIT_COUNT - 1]).encode_index_list(&mut buf[..1]),
+        Err(CodecError::BufferTooSmall)
+    );
+
+    assert_eq!(
+        Bm::decode_index_list(&[0xe8]),
+        Err(CodecError::UnexpectedEnd)
+    );
+    assert_eq!(
+        Bm::decode_index_list(&[0xe8, 0x07]),
+        Err(CodecError::OutOfRange)
+    );
+    assert_eq!(
+        Bm::decode_index_list(&[0xe7, 0x07]),
+        Ok(Bm::from_ones_iter([BIT_COUNT - 1]))
+    );
+    assert_eq!(
+        Bm::decode_index_list(&[0xe7, 0x07, 0]),
+        Err(CodecError::OutOfRange)
+    );
+}
+
+#[test]
+fn test_compact() {
+    const BIT_COUNT: usize = 1000;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let mut buf = [0u8; Bm::max_compact_len()];
+
+    let random: Bm = (0..BIT_COUNT).map(|i| i * 7919 % 13 < 6).collect();
+    let mut runs = Bm::new();
+    runs.set_range(10..500);
+    runs.set_range(600..610);
+    let mut nearly_full = Bm::with_all_set();
+    nearly_full.unset(17);
+    nearly_full.unset(600);
+    for (bitmap, tag, len) in [
+        (random, 0, 1 + bucket_count(BIT_COUNT)),
+        (Bm::new(), 1, 1),
+        (Bm::from_ones_iter([5, 700]), 1, 4),
+        (nearly_full, 2, 4),
+        (Bm::with_all_set(), 2, 1),
+        (runs, 3, 8),
+    ] {
+        assert_eq!(bitmap.encode_compact(&mut buf), Ok(len));
+        assert_eq!(buf[0], tag);
+        assert_eq!(Bm::decode_compact(&buf[..len]), Ok(bitmap));
+        if len > 1 {
+            assert_eq!(
+                bitmap.encode_compact(&mut buf[..len - 1]),
+                Err(CodecError::BufferTooSmall)
+            );
+        }
+    }
+    assert_eq!(
+        Bm::new().encode_compact(&mut []),
+        Err(CodecError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_compact_invalid() {
+    type Bm = BitMap<10, { bucket_count(10) }>;
+    assert_eq!(Bm::decode_compact(&[]), Err(CodecError::UnexpectedEnd));
+    assert_eq!(Bm::decode_compact(&[4]), Err(CodecError::InvalidTag));
+    assert_eq!(Bm::decode_compact(&[0, 1]), Err(CodecError::UnexpectedEnd));
+    assert_eq!(
+        Bm::decode_compact(&[0, 1, 2, 3]),
+        Err(CodecError::TrailingBytes)
+    );
+    assert_eq!(Bm::decode_compact(&[0, 1, 4]), Err(CodecError::OutOfRange));
+    assert_eq!(Bm::decode_compact(&[2, 10]), Err(CodecError::OutOfRange));
+    assert_eq!(Bm::decode_compact(&[3, 4]), Err(CodecError::UnexpectedEnd));
+    assert_eq!(Bm::decode_compact(&[3, 10]), Ok(Bm::new()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_roaring_insert_remove() {
+    use alloc::vec::Vec;
+    use roaring::ContainerKind;
+
+    let mut set = RoaringBitMap::new();
+    assert!(set.is_empty());
+    // grows past the array limit of 4096 values into a bitmap container
+    for (i, value) in (0..10_000).map(|i| 65_536 * 3 + i * 6).enumerate() {
+        assert!(set.insert(value));
+        let kind = if i < 4_096 {
+            ContainerKind::Array
+        } else {
+            ContainerKind::Bitmap
+        };
+        assert_eq!(set.container_kind(3), Some(kind));
+    }
+    assert!(!set.insert(65_536 * 3));
+    assert_eq!(set.len(), 10_000);
+    assert!(set.contains(65_536 * 3 + 59_994));
+    assert!(!set.contains(65_536 * 3 + 59_995));
+    for value in (0..10_000)
+        .map(|i| 65_536 * 3 + i * 6)
+        .filter(|v| v % 4 != 0)
+    {
+        assert!(set.remove(value));
+    }
+    assert_eq!(set.len(), 5_000);
+    assert!(
+        set.iter().eq((0..10_000)
+            .map(|i| 65_536 * 3 + i * 6)
+            .filter(|v| v % 4 == 0))
+    );
+
+    // and shrinks back into an array container
+    let values: Vec<u32> = set.iter().collect();
+    for &value in &values[..903] {
+        assert!(set.remove(value));
+    }
+    assert_eq!(set.len(), 4_097);
+    assert_eq!(set.container_kind(3), Some(ContainerKind::Bitmap));
+    assert!(set.remove(values[903]));
+    assert_eq!(set.container_kind(3), Some(ContainerKind::Array));
+    assert!(set.iter().eq(values[904..].iter().copied()));
+
+    set.clear();
+    set.extend([u32::MAX, 0, 65_535, 65_536, 1 << 31]);
+    let values: Vec<u32> = set.iter().collect();
+    assert_eq!(values, [0, 65_535, 65_536, 1 << 31, u32::MAX]);
+    assert!(set.remove(u32::MAX));
+    assert!(!set.remove(u32::MAX));
+    assert!(!set.remove(7));
+    assert_eq!(set.len(), 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_roaring_runs() {
+    use roaring::ContainerKind;
+
+    let mut set: RoaringBitMap = (1_000..61_000).chain([62_000, 65_535]).collect();
+    let before = set.clone();
+    assert_eq!(set.container_kind(0), Some(ContainerKind::Bitmap));
+    set.optimize();
+    assert_eq!(set.container_kind(0), Some(ContainerKind::Run));
+    assert_eq!(set, before);
+
+    // edits inside, at the edges and between runs of a run container
+    for value in [999, 61_000, 61_001, 62_001, 0, 63_000] {
+        assert!(set.insert(value));
+    }
+    for value in [1_000, 30_000, 61_001, 65_535, 62_000] {
+        assert!(set.remove(value));
+    }
+    assert!(!set.insert(50_000));
+    assert!(!set.remove(61_500));
+    let expected = [0, 999]
+        .into_iter()
+        .chain(1_001..30_000)
+        .chain(30_001..61_001)
+        .chain([62_001, 63_000]);
+    assert!(set.iter().eq(expected.clone()));
+    assert_eq!(set.len(), expected.count() as u64);
+    assert_eq!(set.container_kind(0), Some(ContainerKind::Run));
+
+    // too many runs for a run container, which holds at most 2048 runs
+    let mut set: RoaringBitMap = (0..1_000).collect();
+    assert_eq!(set.container_kind(0), Some(ContainerKind::Array));
+    set.optimize();
+    assert_eq!(set.container_kind(0), Some(ContainerKind::Run));
+    for (i, value) in (0..3_000).map(|i| 2_000 + 2 * i).enumerate() {
+        assert!(set.insert(value));
+        let run_count = 2 + i;
+        let kind = if run_count <= 2_048 {
+            ContainerKind::Run
+        } else {
+            ContainerKind::Array
+        };
+        assert_eq!(set.container_kind(0), Some(kind));
+    }
+    assert_eq!(set.len(), 4_000);
+    assert!(
+        set.iter()
+            .eq((0..1_000).chain((0..3_000).map(|i| 2_000 + 2 * i)))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_roaring_bitmap_interop() {
+    const BIT_COUNT: usize = 200_000;
+    type Bm = BitMap<BIT_COUNT, { bucket_count(BIT_COUNT) }>;
+    let mut bitmap = Bm::new();
+    bitmap.set_range(10..70_000);
+    bitmap.set_range(150_000..150_100);
+    for idx in (100_000..110_000).step_by(3) {
+        bitmap.set(idx);
+    }
+
+    let mut set = RoaringBitMap::new();
+    set.insert(5);
+    set.insert(1_000);
+    set.insert_bitmap(100, &bitmap);
+    assert_eq!(set.len(), 1 + bitmap.popcount() as u64);
+    assert!(
+        set.iter().eq([5]
+            .into_iter()
+            .chain(bitmap.iter_ones().map(|i| i as u32 + 100)))
+    );
+
+    assert_eq!(
+        set.extract_bitmap::<BIT_COUNT, { bucket_count(BIT_COUNT) }>(100),
+        bitmap
+    );
+    let head: BitMap<16, 2> = set.extract_bitmap(0);
+    assert_eq!(head, BitMap::from_ones_iter([5]));
+    let tail: BitMap<16, 2> = set.extract_bitmap(u32::MAX - 15);
+    assert_eq!(tail, BitMap::new());
+
+    assert!(set.chunk(2).iter_ones().eq(19_028..19_128));
+    assert_eq!(set.chunk(9).popcount(), 0);
+
+    set.insert_bitmap(u32::MAX - 7, &BitMap::<8, 1>::with_all_set());
+    assert!(set.contains(u32::MAX));
+    set.insert_bitmap(0, &Bm::new());
+    assert_eq!(set.len(), 9 + bitmap.popcount() as u64);
+
+    // windows cutting into array, run and bitmap containers
+    set.extend([400_000, 400_003, 400_036]);
+    for offset in [
+        0,
+        95,
+        65_530,
+        70_090,
+        100_095,
+        131_060,
+        150_090,
+        399_999,
+        u32::MAX - 36,
+    ] {
+        let window: BitMap<37, { bucket_count(37) }> = set.extract_bitmap(offset);
+        let expected = (0..37).filter(|&i| set.contains(offset + i as u32));
+        assert_eq!(window, BitMap::from_ones_iter(expected));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic(expected = "Bitmap of 16 bits does not fit at offset 4294967281")]
+fn test_roaring_insert_bitmap_out_of_range() {
+    let mut set = RoaringBitMap::new();
+    set.insert_bitmap(u32::MAX - 14, &BitMap::<16, 2>::new());
+}
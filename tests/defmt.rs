@@ -0,0 +1,62 @@
+//! Checks the bytes `defmt::Format` for `BitMap` sends, recording them with a
+//! global defmt logger. The crate itself forbids the `unsafe` a logger needs,
+//! so this lives outside of `src/tests.rs`.
+#![cfg(feature = "defmt")]
+
+use light_bitmap::{BitMap, bucket_count};
+use std::sync::Mutex;
+
+static BYTES: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+defmt::timestamp!("");
+
+#[defmt::global_logger]
+struct RecordingLogger;
+
+unsafe impl defmt::Logger for RecordingLogger {
+    fn acquire() {}
+
+    unsafe fn flush() {}
+
+    unsafe fn release() {}
+
+    unsafe fn write(bytes: &[u8]) {
+        BYTES.lock().unwrap().extend_from_slice(bytes);
+    }
+}
+
+/// Logs `bitmap` and returns the bytes sent, skipping the interned format
+/// string indices of `println!`, the timestamp and the `Format` impl.
+fn encode<const BIT_COUNT: usize, const BUCKET_COUNT: usize>(
+    bitmap: &BitMap<BIT_COUNT, BUCKET_COUNT>,
+) -> Vec<u8> {
+    let mut bytes = BYTES.lock().unwrap();
+    bytes.clear();
+    drop(bytes);
+    defmt::println!("{}", bitmap);
+    BYTES.lock().unwrap().split_off(6)
+}
+
+/// The bucket slice as `u32` length and bytes, `BIT_COUNT` as `u32` and the
+/// 2 byte end marker of the format sequence.
+fn expected(buckets: &[u8], bit_count: u32) -> Vec<u8> {
+    let mut bytes = (buckets.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(buckets);
+    bytes.extend_from_slice(&bit_count.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes
+}
+
+#[test]
+fn test_defmt_sends_reversed_buckets_and_bit_count_once() {
+    let bitmap = BitMap::<9, { bucket_count(9) }>::from_ones_iter([0, 2, 7, 8]);
+    assert_eq!(encode(&bitmap), expected(&[0b1010_0001, 0b1000_0000], 9));
+
+    let mut bitmap = BitMap::<300, { bucket_count(300) }>::new();
+    bitmap.set_range(8..16);
+    bitmap.set(299);
+    let mut buckets = [0; bucket_count(300)];
+    buckets[1] = 0xff;
+    buckets[37] = 0b0001_0000;
+    assert_eq!(encode(&bitmap), expected(&buckets, 300));
+}
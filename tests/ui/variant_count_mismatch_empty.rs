@@ -0,0 +1,23 @@
+use light_bitmap::{bucket_count, BitIndexed, EnumSet};
+
+#[derive(Clone, Copy)]
+enum Pin {
+    A,
+    B,
+}
+
+impl BitIndexed for Pin {
+    const VARIANT_COUNT: usize = 2;
+
+    fn to_index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(idx: usize) -> Option<Self> {
+        [Pin::A, Pin::B].get(idx).copied()
+    }
+}
+
+const PINS: EnumSet<Pin, 3, { bucket_count(3) }> = EnumSet::const_empty();
+
+fn main() {}